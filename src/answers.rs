@@ -0,0 +1,95 @@
+use crate::{Error, Result};
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// The accepted answers for a single input, stored as one `partN: answer`
+/// line per part so that the files can be edited and reviewed by hand. Lines
+/// starting with `#` are comments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    parts: [Option<String>; 2],
+}
+
+/// The outcome of comparing a computed answer with the accepted one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    New,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => return Err(e.into()),
+        };
+        contents.parse()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    pub fn get(&self, part: usize) -> Option<&str> {
+        self.parts[part - 1].as_deref()
+    }
+
+    pub fn set(&mut self, part: usize, answer: String) {
+        self.parts[part - 1] = Some(answer);
+    }
+
+    pub fn verify(&self, part: usize, answer: &str) -> Verdict {
+        match self.get(part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.to_owned()),
+            None => Verdict::New,
+        }
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Answers> {
+        let mut answers = Answers::default();
+        for line in s.lines().filter(|line| !line.trim().is_empty() && !line.starts_with('#')) {
+            let idx = line.find(": ").ok_or(Error::input("expected 'partN: answer'"))?;
+            let part = match &line[..idx] {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(Error::input("expected part1 or part2")),
+            };
+            answers.set(part, line[idx + 2..].to_owned());
+        }
+        Ok(answers)
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (idx, answer) in self.parts.iter().enumerate() {
+            if let Some(answer) = answer {
+                writeln!(f, "part{}: {}", idx + 1, answer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn answers_test() {
+    let answers: Answers = "# day01\npart1: 232\npart2: abcdffaa\n".parse().unwrap();
+    assert_eq!(answers.get(1), Some("232"));
+    assert_eq!(answers.get(2), Some("abcdffaa"));
+    assert_eq!(answers.to_string().parse::<Answers>().unwrap(), answers);
+
+    assert_eq!(answers.verify(1, "232"), Verdict::Pass);
+    assert_eq!(answers.verify(1, "233"), Verdict::Fail("232".to_owned()));
+    assert_eq!(Answers::default().verify(1, "232"), Verdict::New);
+}
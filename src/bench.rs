@@ -0,0 +1,192 @@
+use crate::framework::Framework;
+use crate::Result;
+use colored::*;
+use reqwest::blocking::Client;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub warmup: usize,
+    pub iterations: usize,
+    /// Relative slowdown of the median before a part is flagged, e.g. `0.1`.
+    pub threshold: f64,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: Option<PathBuf>,
+}
+
+/// Timing statistics over all measured iterations of a single part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty());
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Stats {
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Median timings of a previous run, keyed by `dayXX partN`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    medians: BTreeMap<String, f64>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn insert(&mut self, label: &str, stats: &Stats) {
+        self.medians.insert(label.to_owned(), stats.median.as_secs_f64());
+    }
+
+    /// Relative change of the median compared with the baseline, e.g. `0.25`
+    /// when the part became 25% slower.
+    pub fn change(&self, label: &str, stats: &Stats) -> Option<f64> {
+        let baseline = *self.medians.get(label)?;
+        if baseline <= 0.0 {
+            return None;
+        }
+        Some(stats.median.as_secs_f64() / baseline - 1.0)
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.2}s", secs)
+    } else if secs >= 1e-3 {
+        format!("{:.2}ms", secs * 1e3)
+    } else {
+        format!("{:.2}µs", secs * 1e6)
+    }
+}
+
+/// Prints one row of the benchmark table. Returns whether the part was
+/// flagged as slower than the baseline by more than `threshold`.
+pub fn print_row(label: &str, stats: &Stats, baseline: Option<&Baseline>, threshold: f64) -> bool {
+    let change = baseline.and_then(|baseline| baseline.change(label, stats));
+    let (change, is_slower) = match change {
+        Some(change) if change > threshold => (
+            format!("{:+.1}% SLOWER", change * 100.0).bright_red().to_string(),
+            true,
+        ),
+        Some(change) if change < -threshold => (
+            format!("{:+.1}% faster", change * 100.0).bright_green().to_string(),
+            false,
+        ),
+        Some(change) => (format!("{:+.1}%", change * 100.0), false),
+        None => (String::new(), false),
+    };
+    println!(
+        "{:<14} {:>10} {:>10} {:>10} {:>10}  {}",
+        label.bright_cyan().bold(),
+        format_duration(stats.min),
+        format_duration(stats.median),
+        format_duration(stats.mean),
+        format_duration(stats.stddev),
+        change,
+    );
+    is_slower
+}
+
+pub fn print_header() {
+    println!(
+        "{:<14} {:>10} {:>10} {:>10} {:>10}",
+        "", "min", "median", "mean", "stddev"
+    );
+}
+
+/// Benchmarks the given days (optionally with an input variant), printing
+/// the results of each day as it completes followed by a table of all parts.
+/// A day that fails is reported and skipped. Returns whether every day ran
+/// and no part was flagged as slower than the baseline.
+pub fn run(fw: &mut Framework, client: &Client, days: &[(&str, Option<&str>)], options: &Options) -> Result<bool> {
+    let baseline = match &options.baseline {
+        Some(path) => Some(Baseline::load(path)?),
+        None => None,
+    };
+
+    let mut results = Vec::new();
+    let mut all_ran = true;
+    for &(day, variant) in days {
+        let day_results = match fw.bench(client, day, variant, options.warmup, options.iterations) {
+            Ok(day_results) => day_results,
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_red());
+                all_ran = false;
+                continue;
+            }
+        };
+        println!();
+        print_header();
+        for (label, stats) in &day_results {
+            print_row(label, stats, baseline.as_ref(), options.threshold);
+        }
+        results.extend(day_results);
+    }
+
+    println!("\n{}", "Total".bright_cyan().bold());
+    print_header();
+    let mut is_slower = false;
+    for (label, stats) in &results {
+        is_slower |= print_row(label, stats, baseline.as_ref(), options.threshold);
+    }
+    let total: Duration = results.iter().map(|(_, stats)| stats.median).sum();
+    println!("{:<14} {:>21}", "total".bright_cyan().bold(), format_duration(total));
+
+    if let Some(path) = &options.save_baseline {
+        let mut new_baseline = Baseline::default();
+        for (label, stats) in &results {
+            new_baseline.insert(label, stats);
+        }
+        new_baseline.save(path)?;
+    }
+
+    Ok(all_ran && !is_slower)
+}
+
+#[test]
+fn stats_test() {
+    let ms = Duration::from_millis;
+    let stats = Stats::from_samples(&mut [ms(4), ms(2), ms(6), ms(8)]);
+    assert_eq!(stats.min, ms(2));
+    assert_eq!(stats.median, ms(5));
+    assert!((stats.mean.as_secs_f64() * 1e3 - 5.0).abs() < 1e-6);
+    assert!((stats.stddev.as_secs_f64() * 1e3 - 5f64.sqrt()).abs() < 1e-6);
+
+    let mut baseline = Baseline::default();
+    baseline.insert("day01 part1", &Stats::from_samples(&mut [ms(4)]));
+    assert!((baseline.change("day01 part1", &stats).unwrap() - 0.25).abs() < 1e-9);
+    assert_eq!(baseline.change("day01 part2", &stats), None);
+}
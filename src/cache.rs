@@ -0,0 +1,312 @@
+use crate::framework::input_hash;
+use crate::{Error, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version of the manifest and directory layout. Caches written by a newer
+/// version are refused rather than misread.
+const VERSION: u32 = 1;
+
+/// Downloaded inputs, stored as one plain file per URL next to a
+/// `manifest.json` that records where each URL is stored, when it was fetched
+/// and the SHA-256 of its contents.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    dir: PathBuf,
+    manifest: Manifest,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    entries: BTreeMap<String, Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Name of the file in the cache directory.
+    pub file: String,
+    /// Unix time in seconds, unknown for entries migrated from `cache.dat`.
+    pub fetched_at: Option<u64>,
+    pub hash: String,
+}
+
+/// The state of the file behind an entry, as shown by `cache list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryStatus {
+    Ok,
+    Missing,
+    /// The contents no longer match the hash in the manifest.
+    Modified,
+}
+
+/// The format of `cache export` and `cache import`, a single JSON file that
+/// can be moved to another machine.
+#[derive(Debug, Serialize, Deserialize)]
+struct Export {
+    version: u32,
+    entries: Vec<ExportedEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportedEntry {
+    url: String,
+    fetched_at: Option<u64>,
+    contents: String,
+}
+
+impl InputCache {
+    /// Opens the cache in `dir`, which is created when the first entry is
+    /// inserted.
+    pub fn open(dir: PathBuf) -> Result<InputCache> {
+        let manifest = match fs::read_to_string(dir.join("manifest.json")) {
+            Ok(contents) => {
+                let manifest: Manifest = serde_json::from_str(&contents)
+                    .map_err(|e| Error::InvalidCache(format!("{}: {}", dir.join("manifest.json").display(), e)))?;
+                check_version(manifest.version)?;
+                manifest
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Manifest {
+                version: VERSION,
+                entries: BTreeMap::new(),
+            },
+            Err(e) => return Err(e.into()),
+        };
+        Ok(InputCache { dir, manifest })
+    }
+
+    /// Moves the entries of the old bincode `cache.dat` into the cache, and
+    /// renames it to `cache.dat.migrated` so this only happens once. Returns
+    /// the number of migrated entries.
+    pub fn migrate(&mut self, legacy: &Path) -> Result<usize> {
+        let data = match fs::read(legacy) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        let entries = bincode::deserialize::<HashMap<String, String>>(&data).map_err(|e| {
+            Error::InvalidCache(format!("{} could not be migrated ({}), remove it", legacy.display(), e))
+        })?;
+
+        let mut count = 0;
+        for (url, contents) in entries {
+            if !self.manifest.entries.contains_key(&url) {
+                self.write_entry(&url, &contents, None)?;
+                count += 1;
+            }
+        }
+        self.save()?;
+
+        let mut migrated = legacy.as_os_str().to_owned();
+        migrated.push(".migrated");
+        fs::rename(legacy, migrated)?;
+        Ok(count)
+    }
+
+    /// The cached contents of `url`. An entry whose file is missing or was
+    /// modified counts as not cached, so that it's downloaded again.
+    pub fn get(&self, url: &str) -> Result<Option<String>> {
+        let entry = match self.manifest.entries.get(url) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        match fs::read_to_string(self.dir.join(&entry.file)) {
+            Ok(contents) if input_hash(&contents) == entry.hash => Ok(Some(contents)),
+            Ok(_) => Ok(None),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn insert(&mut self, url: &str, contents: &str) -> Result<()> {
+        self.write_entry(url, contents, Some(now()))?;
+        self.save()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &Entry)> {
+        self.manifest.entries.iter().map(|(url, entry)| (url.as_str(), entry))
+    }
+
+    pub fn status(&self, entry: &Entry) -> Result<EntryStatus> {
+        match fs::read_to_string(self.dir.join(&entry.file)) {
+            Ok(contents) if input_hash(&contents) == entry.hash => Ok(EntryStatus::Ok),
+            Ok(_) => Ok(EntryStatus::Modified),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(EntryStatus::Missing),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn path(&self, entry: &Entry) -> PathBuf {
+        self.dir.join(&entry.file)
+    }
+
+    /// Removes `url` from the cache, returning whether it was cached.
+    pub fn evict(&mut self, url: &str) -> Result<bool> {
+        let entry = match self.manifest.entries.remove(url) {
+            Some(entry) => entry,
+            None => return Ok(false),
+        };
+        remove_file(&self.dir.join(&entry.file))?;
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Removes every entry, returning how many there were.
+    pub fn clear(&mut self) -> Result<usize> {
+        let count = self.manifest.entries.len();
+        for entry in self.manifest.entries.values() {
+            remove_file(&self.dir.join(&entry.file))?;
+        }
+        self.manifest.entries.clear();
+        self.save()?;
+        Ok(count)
+    }
+
+    /// Writes all intact entries to a single JSON file, returning how many.
+    pub fn export(&self, path: &Path) -> Result<usize> {
+        let mut entries = Vec::new();
+        for (url, entry) in &self.manifest.entries {
+            if let Some(contents) = self.get(url)? {
+                entries.push(ExportedEntry {
+                    url: url.clone(),
+                    fetched_at: entry.fetched_at,
+                    contents,
+                });
+            }
+        }
+        let count = entries.len();
+        let export = Export {
+            version: VERSION,
+            entries,
+        };
+        fs::write(path, serde_json::to_string_pretty(&export)?)?;
+        Ok(count)
+    }
+
+    /// Adds the entries of a file written by `export`, replacing entries for
+    /// the same URLs. Returns how many were imported.
+    pub fn import(&mut self, path: &Path) -> Result<usize> {
+        let export: Export = serde_json::from_str(&fs::read_to_string(path)?)?;
+        check_version(export.version)?;
+        for entry in &export.entries {
+            self.write_entry(&entry.url, &entry.contents, entry.fetched_at)?;
+        }
+        self.save()?;
+        Ok(export.entries.len())
+    }
+
+    fn write_entry(&mut self, url: &str, contents: &str, fetched_at: Option<u64>) -> Result<()> {
+        let file = match self.manifest.entries.get(url) {
+            Some(entry) => entry.file.clone(),
+            None => self.file_name(url),
+        };
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(&file), contents)?;
+        let entry = Entry {
+            file,
+            fetched_at,
+            hash: input_hash(contents),
+        };
+        self.manifest.entries.insert(url.to_owned(), entry);
+        Ok(())
+    }
+
+    /// A readable file name for a new entry, e.g. `adventofcode.com_2015_day_1_input.txt`
+    /// for `https://adventofcode.com/2015/day/1/input`.
+    fn file_name(&self, url: &str) -> String {
+        let path = url.splitn(2, "://").last().unwrap_or(url);
+        let base = path
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+            .collect::<String>();
+        let is_taken = |name: &str| self.manifest.entries.values().any(|entry| entry.file == name);
+        let mut name = format!("{}.txt", base);
+        let mut suffix = 2;
+        while is_taken(&name) {
+            name = format!("{}_{}.txt", base, suffix);
+            suffix += 1;
+        }
+        name
+    }
+
+    /// Writes the manifest through a temporary file, so that an interrupted
+    /// write doesn't leave a truncated manifest behind.
+    fn save(&self) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join("manifest.json");
+        let temp = self.dir.join("manifest.json.tmp");
+        fs::write(&temp, serde_json::to_string_pretty(&self.manifest)?)?;
+        fs::rename(temp, path)?;
+        Ok(())
+    }
+}
+
+fn check_version(version: u32) -> Result<()> {
+    if version > VERSION {
+        return Err(Error::InvalidCache(format!(
+            "version {} is newer than the supported version {}",
+            version, VERSION
+        )));
+    }
+    Ok(())
+}
+
+fn remove_file(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[test]
+fn input_cache_test() {
+    let dir = crate::mock_server::temp_dir("input_cache");
+    let url = "https://adventofcode.com/2015/day/1/input";
+
+    let mut legacy = HashMap::new();
+    legacy.insert(url.to_owned(), "((())".to_owned());
+    fs::write(dir.join("cache.dat"), bincode::serialize(&legacy).unwrap()).unwrap();
+
+    let mut cache = InputCache::open(dir.join("cache")).unwrap();
+    assert_eq!(cache.migrate(&dir.join("cache.dat")).unwrap(), 1);
+    assert!(!dir.join("cache.dat").exists());
+    assert!(dir.join("cache.dat.migrated").exists());
+
+    // Survives reopening, and stores the input as a plain file
+    let mut cache = InputCache::open(dir.join("cache")).unwrap();
+    assert_eq!(cache.get(url).unwrap().as_deref(), Some("((())"));
+    let (_, entry) = cache.entries().next().unwrap();
+    assert_eq!(entry.file, "adventofcode.com_2015_day_1_input.txt");
+    assert_eq!(entry.fetched_at, None);
+    let path = cache.path(entry);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "((())");
+
+    // A modified file is no longer trusted
+    fs::write(&path, "(((").unwrap();
+    assert_eq!(cache.status(&cache.manifest.entries[url]).unwrap(), EntryStatus::Modified);
+    assert_eq!(cache.get(url).unwrap(), None);
+
+    cache.insert(url, "()").unwrap();
+    let export = dir.join("export.json");
+    assert_eq!(cache.export(&export).unwrap(), 1);
+    assert_eq!(cache.clear().unwrap(), 1);
+    assert_eq!(cache.get(url).unwrap(), None);
+    assert!(!path.exists());
+
+    assert_eq!(cache.import(&export).unwrap(), 1);
+    assert_eq!(cache.get(url).unwrap().as_deref(), Some("()"));
+    assert!(cache.evict(url).unwrap());
+    assert!(!cache.evict(url).unwrap());
+
+    fs::write(dir.join("cache/manifest.json"), r#"{"version":2,"entries":{}}"#).unwrap();
+    assert!(InputCache::open(dir.join("cache")).is_err());
+}
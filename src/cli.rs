@@ -0,0 +1,942 @@
+use advent_of_code_2015::bench;
+use advent_of_code_2015::cache::EntryStatus;
+use advent_of_code_2015::config::{self, Config, Environment, Overrides};
+use advent_of_code_2015::framework::{self, Framework, PartResult};
+use advent_of_code_2015::output::{self, Format, Record, Status};
+use advent_of_code_2015::submit::Outcome;
+use advent_of_code_2015::watch;
+use colored::*;
+use reqwest::blocking::Client;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const USAGE: &str = "\
+Usage: advent_of_code_2015 [COMMAND] [DAYS...] [OPTIONS]
+
+Commands:
+  run [DAYS...]          Run the given days, or all days (the default command)
+  check [DAYS...]        Compare the answers with the accepted answers
+  bench [DAYS...]        Measure how long each part takes
+  submit DAY PART        Submit the answer to a part, e.g. submit day05 part2
+  describe DAY           Show the puzzle description
+  watch DAY              Run a day again whenever its input changes
+  generate DAY           Print a random input for a day
+  examples [DAYS...]     Check the answers on the examples in examples/
+  examples extract [DAYS...]
+                         Add the code blocks in the puzzle descriptions to
+                         the examples
+  cache list             List the cached inputs
+  cache show DAY|URL     Print a cached input
+  cache evict DAY|URL... Remove inputs from the cache
+  cache clear            Remove all inputs from the cache
+  cache export PATH      Write the cached inputs to a JSON file
+  cache import PATH      Add the inputs in a JSON file to the cache
+  help                   Show this message
+
+Days are given as 5, day05, day05.<variant> (see inputs/) or an inclusive
+range such as 3..7.
+
+Options:
+  --part N               Only run part N
+  --input PATH           Read the input from PATH, or from stdin if PATH is -
+                         (requires a single day)
+  --no-fetch             Fail instead of downloading inputs
+  --wait                 Wait for locked puzzles to unlock instead of failing
+  --quiet                Only print the answers
+  --lenient              Skip input lines that don't parse instead of failing
+  --format FORMAT        run: print text (default), json, csv or tsv
+  --jobs N               run: run days and parts on N threads (default 1)
+  --timeout SECS         Cancel parts after SECS seconds, 0 disables timeouts
+                         (default: the timeout of the day, if any)
+  --param DAY.NAME=VALUE Override a puzzle constant, e.g. day14.time=1000
+  --accept               check: record the answers of new parts
+  --all-profiles         check: check the days for every profile
+  --warmup N             bench: warmup iterations (default 3)
+  --iterations N         bench: measured iterations (default 10)
+  --threshold PERCENT    bench: slowdown before a part is flagged (default 10)
+  --baseline PATH        bench: compare with a saved baseline
+  --save-baseline PATH   bench: save the medians as a baseline
+  --cargo                watch: run the day with cargo, and rebuild and run it
+                         again whenever src/ changes
+  --interval SECS        watch: how often to check for changes (default 0.5)
+  --seed N               generate: the seed of the random input (default 0)
+  --size N               generate: the size of the random input, such as the
+                         number of lines (default: the size of the real input)
+  --dir PATH             Directory with the token, cache, inputs and answers
+  --config PATH          Config file (default aoc.json in the directory)
+  --profile NAME         Use the token, cache, inputs and answers of a profile
+  --base-url URL         Puzzle server (default https://adventofcode.com)
+  --token-file PATH      File containing the session token
+  --fetch-interval SECS  Minimum time between downloads (default 5)
+  -h, --help             Show this message
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    Check,
+    Bench,
+    Submit,
+    Describe,
+    Watch,
+    Generate,
+    Examples,
+    Cache,
+    Help,
+}
+
+/// What the `examples` command does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExamplesAction {
+    Check,
+    Extract,
+}
+
+/// The options of the `generate` command.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerateOptions {
+    pub seed: Option<u64>,
+    pub size: Option<usize>,
+}
+
+/// What the `cache` command does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheAction {
+    List,
+    Show(CacheKey),
+    Evict(Vec<CacheKey>),
+    Clear,
+    Export(PathBuf),
+    Import(PathBuf),
+}
+
+/// A cache entry, given by the day whose input it is or by its URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheKey {
+    Day(String),
+    Url(String),
+}
+
+/// A day to run, optionally with a named input variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    pub day: String,
+    pub variant: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Args {
+    pub command: Command,
+    pub days: Vec<Selection>,
+    pub options: framework::Options,
+    /// Parameter overrides, as `(day, name, value)`.
+    pub params: Vec<(String, String, String)>,
+    pub accept: bool,
+    pub all_profiles: bool,
+    pub bench: bench::Options,
+    pub watch: watch::Options,
+    pub generate: GenerateOptions,
+    pub examples: ExamplesAction,
+    pub cache: CacheAction,
+    pub overrides: Overrides,
+}
+
+impl Args {
+    /// Parses the command line arguments (without the program name), given
+    /// the names of all registered days.
+    pub fn parse(args: Vec<String>, days: &[&str]) -> Result<Args, String> {
+        let mut parsed = Args {
+            command: Command::Run,
+            days: Vec::new(),
+            options: framework::Options {
+                jobs: 1,
+                ..framework::Options::default()
+            },
+            params: Vec::new(),
+            accept: false,
+            all_profiles: false,
+            bench: bench::Options {
+                warmup: 3,
+                iterations: 10,
+                threshold: 0.1,
+                baseline: None,
+                save_baseline: None,
+            },
+            watch: watch::Options {
+                interval: Duration::from_millis(500),
+                cargo: false,
+                args: Vec::new(),
+            },
+            generate: GenerateOptions::default(),
+            examples: ExamplesAction::Check,
+            cache: CacheAction::List,
+            overrides: Overrides::default(),
+        };
+
+        let mut positional = Vec::new();
+        let mut idx = 0;
+        while idx < args.len() {
+            let arg = &args[idx];
+            let start = idx;
+            idx += 1;
+            if !arg.starts_with('-') || arg == "-" {
+                positional.push(arg.as_str());
+                continue;
+            }
+
+            let (name, inline_value) = match arg.find('=') {
+                Some(pos) => (&arg[..pos], Some(arg[pos + 1..].to_owned())),
+                None => (arg.as_str(), None),
+            };
+            let mut value = || match &inline_value {
+                Some(value) => Ok(value.clone()),
+                None if idx < args.len() => {
+                    idx += 1;
+                    Ok(args[idx - 1].clone())
+                }
+                None => Err(format!("missing value for {}", name)),
+            };
+            match name {
+                "-h" | "--help" => parsed.command = Command::Help,
+                "--part" => parsed.options.part = Some(parse_part(&value()?)?),
+                "--input" => parsed.options.input_file = Some(PathBuf::from(value()?)),
+                "--no-fetch" => parsed.options.no_fetch = true,
+                "--wait" => parsed.options.wait = true,
+                "--quiet" => parsed.options.quiet = true,
+                "--lenient" => parsed.options.lenient = true,
+                "--format" => parsed.options.format = value()?.parse()?,
+                "--jobs" => parsed.options.jobs = parse_value(name, &value()?)?,
+                "--timeout" => parsed.options.timeout = Some(parse_duration(name, &value()?)?),
+                "--param" => parsed.params.push(parse_param(&value()?, days)?),
+                "--accept" => parsed.accept = true,
+                "--all-profiles" => parsed.all_profiles = true,
+                "--warmup" => parsed.bench.warmup = parse_value(name, &value()?)?,
+                "--iterations" => parsed.bench.iterations = parse_value(name, &value()?)?,
+                "--threshold" => parsed.bench.threshold = parse_value::<f64>(name, &value()?)? / 100.0,
+                "--baseline" => parsed.bench.baseline = Some(PathBuf::from(value()?)),
+                "--save-baseline" => parsed.bench.save_baseline = Some(PathBuf::from(value()?)),
+                "--cargo" => parsed.watch.cargo = true,
+                "--interval" => {
+                    parsed.watch.interval = parse_duration(name, &value()?)?;
+                    if parsed.watch.interval.is_zero() {
+                        return Err(format!("invalid value for {}: 0", name));
+                    }
+                }
+                "--seed" => parsed.generate.seed = Some(parse_value(name, &value()?)?),
+                "--size" => parsed.generate.size = Some(parse_value(name, &value()?)?),
+                "--dir" => parsed.overrides.dir = Some(PathBuf::from(value()?)),
+                "--config" => parsed.overrides.config = Some(PathBuf::from(value()?)),
+                "--profile" => parsed.overrides.profile = Some(value()?),
+                "--base-url" => parsed.overrides.base_url = Some(value()?),
+                "--token-file" => parsed.overrides.token_file = Some(PathBuf::from(value()?)),
+                "--fetch-interval" => {
+                    let secs = value()?;
+                    parsed.overrides.fetch_interval =
+                        Some(config::parse_interval(&secs).map_err(|e| e.to_string())?);
+                }
+                _ => return Err(format!("unknown option {}", name)),
+            }
+            // Everything but the options of watch itself is passed on to the
+            // runs of watch --cargo
+            if name != "--cargo" && name != "--interval" {
+                parsed.watch.args.extend_from_slice(&args[start..idx]);
+            }
+        }
+
+        if parsed.command == Command::Help {
+            return Ok(parsed);
+        }
+        let command = match positional.first() {
+            Some(&"run") => Some(Command::Run),
+            Some(&"check") => Some(Command::Check),
+            Some(&"bench") => Some(Command::Bench),
+            Some(&"submit") => Some(Command::Submit),
+            Some(&"describe") => Some(Command::Describe),
+            Some(&"watch") => Some(Command::Watch),
+            Some(&"generate") => Some(Command::Generate),
+            Some(&"examples") => Some(Command::Examples),
+            Some(&"cache") => Some(Command::Cache),
+            Some(&"help") => Some(Command::Help),
+            _ => None,
+        };
+        if let Some(command) = command {
+            parsed.command = command;
+            positional.remove(0);
+        }
+
+        match parsed.command {
+            Command::Help => return Ok(parsed),
+            Command::Submit => {
+                if positional.len() != 2 {
+                    return Err("usage: submit DAY PART".to_owned());
+                }
+                parsed.days.push(Selection {
+                    day: parse_day(positional[0], days)?,
+                    variant: None,
+                });
+                parsed.options.part = Some(parse_part(positional[1])?);
+            }
+            Command::Describe | Command::Generate => {
+                if positional.len() != 1 {
+                    return Err(match parsed.command {
+                        Command::Describe => "usage: describe DAY".to_owned(),
+                        _ => "usage: generate DAY".to_owned(),
+                    });
+                }
+                parsed.days.push(Selection {
+                    day: parse_day(positional[0], days)?,
+                    variant: None,
+                });
+            }
+            Command::Watch => {
+                let selection = match positional.as_slice() {
+                    [arg] => parse_selection(arg, days)?,
+                    _ => Vec::new(),
+                };
+                if selection.len() != 1 {
+                    return Err("usage: watch DAY".to_owned());
+                }
+                parsed.days = selection;
+            }
+            Command::Cache => parsed.cache = parse_cache_action(&positional, days)?,
+            _ => {
+                if parsed.command == Command::Examples {
+                    match positional.first() {
+                        Some(&"extract") => parsed.examples = ExamplesAction::Extract,
+                        Some(&"check") => parsed.examples = ExamplesAction::Check,
+                        _ => positional.insert(0, "check"),
+                    }
+                    positional.remove(0);
+                }
+                for arg in positional {
+                    parsed.days.extend(parse_selection(arg, days)?);
+                }
+                if parsed.days.is_empty() {
+                    parsed.days.extend(days.iter().map(|&day| Selection {
+                        day: day.to_owned(),
+                        variant: None,
+                    }));
+                }
+            }
+        }
+
+        if parsed.options.format != Format::Text && parsed.command != Command::Run {
+            return Err("--format can only be used with run".to_owned());
+        }
+        if parsed.options.jobs == 0 {
+            return Err("--jobs must be at least 1".to_owned());
+        }
+        if parsed.options.jobs != 1 && parsed.command != Command::Run {
+            return Err("--jobs can only be used with run".to_owned());
+        }
+
+        if parsed.generate != GenerateOptions::default() && parsed.command != Command::Generate {
+            return Err("--seed and --size can only be used with generate".to_owned());
+        }
+
+        if parsed.all_profiles {
+            if parsed.command != Command::Check {
+                return Err("--all-profiles can only be used with check".to_owned());
+            }
+            if parsed.overrides.profile.is_some() {
+                return Err("--all-profiles can't be combined with --profile".to_owned());
+            }
+        }
+
+        if !parsed.params.is_empty() && (parsed.command == Command::Check || parsed.command == Command::Submit) {
+            return Err("--param can't be used to check or submit answers".to_owned());
+        }
+
+        if parsed.options.input_file.is_some() {
+            if parsed.command == Command::Check || parsed.command == Command::Submit {
+                return Err("--input can't be used to check or submit answers".to_owned());
+            }
+            if parsed.days.len() != 1 {
+                return Err("--input requires a single day".to_owned());
+            }
+            if parsed.command == Command::Watch && parsed.options.input_file.as_deref() == Some(Path::new("-")) {
+                return Err("watch can't read the input from stdin".to_owned());
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for {}: {}", name, value))
+}
+
+/// Parses a duration given in (fractional) seconds.
+fn parse_duration(name: &str, value: &str) -> Result<Duration, String> {
+    let secs = parse_value::<f64>(name, value)?;
+    Duration::try_from_secs_f64(secs).map_err(|_| format!("invalid value for {}: {}", name, secs))
+}
+
+fn parse_part(s: &str) -> Result<usize, String> {
+    match s.trim_start_matches("part") {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part {}, expected 1 or 2", s)),
+    }
+}
+
+/// Turns `5`, `05`, `day5` or `day05` into the name of a registered day.
+fn parse_day(s: &str, days: &[&str]) -> Result<String, String> {
+    let number: u32 = s
+        .trim_start_matches("day")
+        .parse()
+        .map_err(|_| format!("invalid day {}", s))?;
+    let name = format!("day{:02}", number);
+    if !days.contains(&name.as_str()) {
+        return Err(format!("{} does not exist", name));
+    }
+    Ok(name)
+}
+
+/// Parses `day14.time=1000` into the day, the parameter and its value.
+fn parse_param(s: &str, days: &[&str]) -> Result<(String, String, String), String> {
+    let (param, value) = s.split_once('=').ok_or_else(|| format!("expected DAY.NAME=VALUE, got {}", s))?;
+    let (day, name) = param.split_once('.').ok_or_else(|| format!("expected DAY.NAME=VALUE, got {}", s))?;
+    Ok((parse_day(day, days)?, name.to_owned(), value.to_owned()))
+}
+
+fn parse_cache_action(args: &[&str], days: &[&str]) -> Result<CacheAction, String> {
+    let key = |arg: &str| {
+        if arg.contains("://") {
+            Ok(CacheKey::Url(arg.to_owned()))
+        } else {
+            parse_day(arg, days).map(CacheKey::Day)
+        }
+    };
+    match args {
+        [] | ["list"] => Ok(CacheAction::List),
+        ["show", arg] => Ok(CacheAction::Show(key(arg)?)),
+        ["evict", rest @ ..] if !rest.is_empty() => {
+            Ok(CacheAction::Evict(rest.iter().map(|arg| key(arg)).collect::<Result<_, _>>()?))
+        }
+        ["clear"] => Ok(CacheAction::Clear),
+        ["export", path] => Ok(CacheAction::Export(PathBuf::from(path))),
+        ["import", path] => Ok(CacheAction::Import(PathBuf::from(path))),
+        _ => Err("usage: cache list|show DAY|evict DAY...|clear|export PATH|import PATH".to_owned()),
+    }
+}
+
+fn parse_selection(arg: &str, days: &[&str]) -> Result<Vec<Selection>, String> {
+    if let Some(idx) = arg.find("..") {
+        let from = parse_day(&arg[..idx], days)?;
+        let to = parse_day(arg[idx + 2..].trim_start_matches('='), days)?;
+        let selections = days
+            .iter()
+            .filter(|&&day| day >= from.as_str() && day <= to.as_str())
+            .map(|&day| Selection {
+                day: day.to_owned(),
+                variant: None,
+            })
+            .collect::<Vec<_>>();
+        if selections.is_empty() {
+            return Err(format!("empty range {}", arg));
+        }
+        return Ok(selections);
+    }
+
+    let (day, variant) = match arg.find('.') {
+        Some(idx) => (&arg[..idx], Some(arg[idx + 1..].to_owned())),
+        None => (arg, None),
+    };
+    Ok(vec![Selection {
+        day: parse_day(day, days)?,
+        variant,
+    }])
+}
+
+/// Runs the parsed command, returning the process exit code.
+pub fn execute(fw: &mut Framework, client: &Client, args: &Args) -> i32 {
+    fw.set_options(args.options.clone());
+    if let Err(e) = fw.set_params(&args.params) {
+        eprintln!("{}", e.to_string().bright_red());
+        return -1;
+    }
+    let days = args
+        .days
+        .iter()
+        .map(|selection| (selection.day.as_str(), selection.variant.as_deref()))
+        .collect::<Vec<_>>();
+
+    match args.command {
+        Command::Help => {
+            print!("{}", USAGE);
+            0
+        }
+        Command::Run => {
+            let mut records = Vec::new();
+            let part_numbers = days
+                .iter()
+                .map(|&(day, _)| fw.part_numbers(day).unwrap_or_default())
+                .collect::<Vec<_>>();
+            let mut index = 0;
+            fw.execute(client, &days, |day, variant, result| {
+                let parts = &part_numbers[index];
+                index += 1;
+                let name = framework::title(day, variant);
+                match result {
+                    Ok(run) => {
+                        let input_hash = run.input_hash;
+                        records.extend(run.parts.into_iter().map(|part| {
+                            let status = part.result.status();
+                            let (answer, error) = match part.result {
+                                PartResult::Answer(answer) => (Some(answer), None),
+                                PartResult::Error(e) => (None, Some(e.to_string())),
+                                PartResult::Panic(message) => (None, Some(message)),
+                                PartResult::Timeout(duration) => {
+                                    (None, Some(format!("timed out after {:?}", duration)))
+                                }
+                            };
+                            Record {
+                                name: name.clone(),
+                                part: part.part,
+                                status,
+                                answer,
+                                duration: Some(part.duration.as_secs_f64()),
+                                error,
+                                input_hash: Some(input_hash.clone()),
+                            }
+                        }))
+                    }
+                    Err(e) => {
+                        if args.options.format == Format::Text {
+                            eprintln!("{}", format!("{}: {}", name, e).bright_red());
+                        }
+                        // A day always gets a record, even if the selected part
+                        // doesn't exist, so that the failure shows up.
+                        let parts = match parts.as_slice() {
+                            [] => vec![args.options.part.unwrap_or(1)],
+                            parts => parts.to_vec(),
+                        };
+                        records.extend(parts.into_iter().map(|part| Record {
+                            name: name.clone(),
+                            part,
+                            status: Status::Err,
+                            answer: None,
+                            duration: None,
+                            error: Some(e.to_string()),
+                            input_hash: None,
+                        }));
+                    }
+                }
+            });
+
+            let all_ok = records.iter().all(|record| record.status == Status::Ok);
+            if args.options.format == Format::Text {
+                if !args.options.quiet && days.len() > 1 {
+                    print_summary(&records);
+                }
+            } else if let Err(e) =
+                output::write_records(args.options.format, &records, std::io::stdout())
+            {
+                eprintln!("{}", e.to_string().bright_red());
+                return -2;
+            }
+            if all_ok {
+                0
+            } else {
+                -2
+            }
+        }
+        Command::Check => {
+            let mut all_passed = true;
+            for &(day, variant) in &days {
+                match fw.check(client, day, variant, args.accept) {
+                    Ok(passed) => all_passed &= passed,
+                    Err(e) => {
+                        eprintln!("{}", e.to_string().bright_red());
+                        all_passed = false;
+                    }
+                }
+            }
+            if all_passed {
+                0
+            } else {
+                1
+            }
+        }
+        Command::Bench => match bench::run(fw, client, &days, &args.bench) {
+            Ok(true) => 0,
+            Ok(false) => 1,
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_red());
+                -2
+            }
+        },
+        Command::Submit => {
+            let (day, part) = (days[0].0, args.options.part.unwrap());
+            match fw.submit(client, day, part) {
+                Ok((answer, outcome)) => {
+                    let verdict = outcome.to_string();
+                    println!(
+                        "{} {} {}",
+                        format!("{} part{}", day, part).bright_cyan().bold(),
+                        answer,
+                        match outcome {
+                            Outcome::Correct => verdict.bright_green(),
+                            _ => verdict.bright_red(),
+                        }
+                    );
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", e.to_string().bright_red());
+                    -2
+                }
+            }
+        }
+        Command::Describe => match fw.describe(client, days[0].0) {
+            Ok(description) => {
+                print!("{}", description);
+                0
+            }
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_red());
+                -2
+            }
+        },
+        Command::Watch => match watch::run(fw, client, days[0].0, days[0].1, &args.watch) {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_red());
+                -2
+            }
+        },
+        Command::Generate => {
+            let generator = advent_of_code_2015::generator(days[0].0).unwrap();
+            println!("{}", generator.run(args.generate.seed.unwrap_or(0), args.generate.size));
+            0
+        }
+        Command::Examples => {
+            let corpus = fw.corpus();
+            let mut all_ok = true;
+            for &(day, _) in &days {
+                let result = match args.examples {
+                    ExamplesAction::Check => fw.check_examples(&corpus, day),
+                    ExamplesAction::Extract => fw.extract_examples(client, &corpus, day).map(|paths| {
+                        for path in &paths {
+                            println!("{} {}", day.bright_cyan().bold(), path.display());
+                        }
+                        true
+                    }),
+                };
+                match result {
+                    Ok(passed) => all_ok &= passed,
+                    Err(e) => {
+                        eprintln!("{}", format!("{}: {}", day, e).bright_red());
+                        all_ok = false;
+                    }
+                }
+            }
+            if all_ok {
+                0
+            } else {
+                1
+            }
+        }
+        Command::Cache => match run_cache(fw, &args.cache) {
+            Ok(true) => 0,
+            Ok(false) => 1,
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_red());
+                -2
+            }
+        },
+    }
+}
+
+/// Runs the `check` command for every profile, and summarizes which days
+/// failed for which profile. Returns the process exit code.
+pub fn check_profiles(
+    config: &Config,
+    env: &Environment,
+    client: &Client,
+    args: &Args,
+    framework: fn(&Config) -> advent_of_code_2015::Result<Framework>,
+) -> i32 {
+    if config.profiles.is_empty() {
+        eprintln!("{}", format!("no profiles in {}", config.path("profiles").display()).bright_red());
+        return 1;
+    }
+
+    let mut summary = Vec::new();
+    for profile in &config.profiles {
+        println!("{}", format!("profile {}", profile).bright_magenta().bold());
+        let mut overrides = args.overrides.clone();
+        overrides.profile = Some(profile.clone());
+        let mut fw = match Config::load(&overrides, env).and_then(|config| framework(&config)) {
+            Ok(fw) => fw,
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_red());
+                summary.push((profile, Err(e.to_string())));
+                continue;
+            }
+        };
+        fw.set_options(args.options.clone());
+
+        let mut failed = Vec::new();
+        for selection in &args.days {
+            let day = selection.day.as_str();
+            match fw.check(client, day, selection.variant.as_deref(), args.accept) {
+                Ok(true) => {}
+                Ok(false) => failed.push(day),
+                Err(e) => {
+                    eprintln!("{}", e.to_string().bright_red());
+                    failed.push(day);
+                }
+            }
+        }
+        summary.push((profile, Ok(failed)));
+    }
+
+    // profile  result
+    // alice    PASS
+    // bob      FAIL day05 day07
+    let width = config.profiles.iter().map(|profile| profile.len()).max().unwrap_or(0).max(7);
+    println!("\n{:<width$}  result", "profile", width = width);
+    let mut all_passed = true;
+    for (profile, result) in summary {
+        print!("{:<width$}  ", profile.bright_magenta().bold(), width = width);
+        match result {
+            Ok(failed) if failed.is_empty() => println!("{}", "PASS".bright_green()),
+            Ok(failed) => {
+                println!("{} {}", "FAIL".bright_red(), failed.join(" "));
+                all_passed = false;
+            }
+            Err(e) => {
+                println!("{} {}", "ERROR".bright_red(), e);
+                all_passed = false;
+            }
+        }
+    }
+    if all_passed {
+        0
+    } else {
+        1
+    }
+}
+
+/// Runs the `cache` command, returning false if the input to show isn't
+/// cached.
+fn run_cache(fw: &mut Framework, action: &CacheAction) -> advent_of_code_2015::Result<bool> {
+    let url = |fw: &Framework, key: &CacheKey| match key {
+        CacheKey::Day(day) => fw.cache_key(day),
+        CacheKey::Url(url) => Ok(url.clone()),
+    };
+    match action {
+        CacheAction::List => {
+            let cache = fw.input_cache();
+            let entries = cache.entries().collect::<Vec<_>>();
+            if entries.is_empty() {
+                println!("the cache is empty");
+                return Ok(true);
+            }
+            let width = entries.iter().map(|(url, _)| url.len()).max().unwrap_or(0);
+            println!("{:<width$}  {:<9}  {:<12}  status", "url", "fetched", "hash", width = width);
+            for (url, entry) in entries {
+                let fetched = entry.fetched_at.map_or("unknown".to_owned(), format_age);
+                print!(
+                    "{:<width$}  {:<9}  {:<12}  ",
+                    url,
+                    fetched,
+                    entry.hash.get(..12).unwrap_or(&entry.hash),
+                    width = width
+                );
+                match cache.status(entry)? {
+                    EntryStatus::Ok => println!("{}", "OK".bright_green()),
+                    EntryStatus::Missing => println!("{}", "MISSING".bright_red()),
+                    EntryStatus::Modified => println!("{}", "MODIFIED".bright_red()),
+                }
+            }
+        }
+        CacheAction::Show(key) => {
+            let url = url(fw, key)?;
+            let cache = fw.input_cache();
+            let contents = match cache.get(&url)? {
+                Some(contents) => contents,
+                None => {
+                    eprintln!("{}", format!("{} is not cached", url).bright_red());
+                    return Ok(false);
+                }
+            };
+            // The details go to stderr, so that the input can be piped
+            let entry = cache.entries().find(|&(key, _)| key == url).unwrap().1;
+            let fetched = entry.fetched_at.map_or("unknown".to_owned(), format_age);
+            eprintln!("{} {}", "file".bright_cyan(), cache.path(entry).display());
+            eprintln!("{} {}", "fetched".bright_cyan(), fetched);
+            eprintln!("{} {}", "hash".bright_cyan(), entry.hash);
+            println!("{}", contents);
+        }
+        CacheAction::Evict(keys) => {
+            for key in keys {
+                let url = url(fw, key)?;
+                if fw.input_cache().evict(&url)? {
+                    println!("evicted {}", url);
+                } else {
+                    println!("{} is not cached", url);
+                }
+            }
+        }
+        CacheAction::Clear => println!("removed {} inputs", fw.input_cache().clear()?),
+        CacheAction::Export(path) => {
+            let count = fw.input_cache().export(path)?;
+            println!("exported {} inputs to {}", count, path.display());
+        }
+        CacheAction::Import(path) => {
+            let count = fw.input_cache().import(path)?;
+            println!("imported {} inputs from {}", count, path.display());
+        }
+    }
+    Ok(true)
+}
+
+/// How long ago a Unix time was, e.g. `3h ago`.
+fn format_age(secs: u64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let age = now.saturating_sub(secs);
+    match age {
+        0..=59 => format!("{}s ago", age),
+        60..=3599 => format!("{}m ago", age / 60),
+        3600..=86399 => format!("{}h ago", age / 3600),
+        _ => format!("{}d ago", age / 86400),
+    }
+}
+
+/// Prints a table with the status of every part, e.g.
+///
+/// ```text
+/// day    part1   part2
+/// day04  OK      TIMEOUT
+/// day08  PANIC   OK
+/// ```
+fn print_summary(records: &[Record]) {
+    let mut names: Vec<&str> = Vec::new();
+    for record in records {
+        if !names.contains(&record.name.as_str()) {
+            names.push(&record.name);
+        }
+    }
+    let width = names.iter().map(|name| name.len()).max().unwrap_or(0).max(3);
+    println!("\n{:<width$}  {:<7} {:<7}", "day", "part1", "part2", width = width);
+    for name in names {
+        print!("{:<width$} ", name.bright_cyan().bold(), width = width);
+        for part in 1..=2 {
+            let status = records
+                .iter()
+                .find(|record| record.name == name && record.part == part)
+                .map_or("-", |record| record.status.as_str());
+            let status = format!(" {:<7}", status);
+            match status.trim() {
+                "OK" => print!("{}", status.bright_green()),
+                "-" => print!("{}", status),
+                _ => print!("{}", status.bright_red()),
+            }
+        }
+        println!();
+    }
+}
+
+#[test]
+fn parse_args_test() {
+    let days = ["day01", "day02", "day03", "day04", "day05", "day12"];
+    let parse = |args: &str| Args::parse(args.split_whitespace().map(str::to_owned).collect(), &days);
+    let names = |args: &Args| args.days.iter().map(|s| s.day.clone()).collect::<Vec<_>>();
+
+    let args = parse("").unwrap();
+    assert_eq!(args.command, Command::Run);
+    assert_eq!(args.days.len(), days.len());
+
+    let args = parse("run 2..4 --part 2 --quiet").unwrap();
+    assert_eq!(names(&args), ["day02", "day03", "day04"]);
+    assert_eq!(args.options.part, Some(2));
+    assert!(args.options.quiet);
+
+    let args = parse("day05.alice 12 --no-fetch --wait").unwrap();
+    assert_eq!(
+        args.days,
+        [
+            Selection { day: "day05".to_owned(), variant: Some("alice".to_owned()) },
+            Selection { day: "day12".to_owned(), variant: None },
+        ]
+    );
+    assert!(args.options.no_fetch);
+    assert!(args.options.wait);
+
+    let args = parse("submit 5 part2").unwrap();
+    assert_eq!(args.command, Command::Submit);
+    assert_eq!(names(&args), ["day05"]);
+    assert_eq!(args.options.part, Some(2));
+
+    assert_eq!(parse("bench day01 --iterations=5").unwrap().bench.iterations, 5);
+    assert_eq!(parse("run --help").unwrap().command, Command::Help);
+    assert!(parse("day13").is_err());
+    assert!(parse("--part 3").is_err());
+    assert!(parse("--input x.txt 1..2").is_err());
+    assert_eq!(parse("day05 --input -").unwrap().options.input_file, Some(PathBuf::from("-")));
+    assert!(parse("--frobnicate").is_err());
+    assert_eq!(parse("--format csv").unwrap().options.format, Format::Csv);
+    assert!(parse("check --format json").is_err());
+    assert_eq!(parse("--jobs 4").unwrap().options.jobs, 4);
+    assert!(parse("--jobs 0").is_err());
+    assert!(parse("bench --jobs 4").is_err());
+    assert_eq!(parse("--timeout 1.5").unwrap().options.timeout, Some(Duration::from_millis(1500)));
+    assert!(parse("--timeout -1").is_err());
+    assert!(parse("--timeout inf").is_err());
+    assert!(parse("--timeout 1e30").is_err());
+    let args = parse("watch day05.alice --cargo --interval 2 --part 1 --dir=x").unwrap();
+    assert_eq!(args.command, Command::Watch);
+    assert_eq!(args.days[0].variant.as_deref(), Some("alice"));
+    assert!(args.watch.cargo);
+    assert_eq!(args.watch.interval, Duration::from_secs(2));
+    assert_eq!(args.watch.args, ["--part", "1", "--dir=x"]);
+    assert!(parse("watch 1..3").is_err());
+    assert!(parse("watch 1 --input -").is_err());
+    assert!(parse("watch 1 --interval 0").is_err());
+    assert!(parse("watch 1 --interval inf").is_err());
+    let args = parse("generate 12 --seed 7 --size=100").unwrap();
+    assert_eq!((args.command, names(&args)), (Command::Generate, vec!["day12".to_owned()]));
+    assert_eq!(args.generate, GenerateOptions { seed: Some(7), size: Some(100) });
+    assert!(parse("generate 1..2").is_err());
+    assert!(parse("run 1 --seed 7").is_err());
+    let args = parse("day12 --param 12.depth=3 --param=day05.x=a=b").unwrap();
+    assert_eq!(
+        args.params,
+        [
+            ("day12".to_owned(), "depth".to_owned(), "3".to_owned()),
+            ("day05".to_owned(), "x".to_owned(), "a=b".to_owned())
+        ]
+    );
+    assert!(parse("--param day12.depth").is_err());
+    assert!(parse("--param depth=3").is_err());
+    assert!(parse("check --param day12.depth=3").is_err());
+    let args = parse("check --all-profiles").unwrap();
+    assert!(args.all_profiles);
+    assert!(parse("run --all-profiles").is_err());
+    assert!(parse("check --all-profiles --profile alice").is_err());
+    assert_eq!(parse("--profile alice").unwrap().overrides.profile.as_deref(), Some("alice"));
+    assert_eq!(parse("cache").unwrap().cache, CacheAction::List);
+    assert_eq!(
+        parse("cache evict 3 https://example.com/x").unwrap().cache,
+        CacheAction::Evict(vec![
+            CacheKey::Day("day03".to_owned()),
+            CacheKey::Url("https://example.com/x".to_owned()),
+        ])
+    );
+    assert_eq!(names(&parse("describe 5").unwrap()), ["day05"]);
+    assert!(parse("describe 1 2").is_err());
+    let args = parse("examples extract 5").unwrap();
+    assert_eq!((args.examples, names(&args)), (ExamplesAction::Extract, vec!["day05".to_owned()]));
+    assert_eq!(parse("examples").unwrap().days.len(), days.len());
+    assert!(parse("cache evict").is_err());
+    assert!(parse("cache show day13").is_err());
+}
@@ -0,0 +1,248 @@
+use crate::{Error, Result};
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_FETCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Where the framework stores its files and how it reaches the puzzle server.
+///
+/// Every setting is resolved from (in increasing order of precedence) the
+/// defaults, the `aoc.json` config file, `AOC_*` environment variables and
+/// command line flags.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Directory containing `token.txt`, `cache`, `inputs`, `answers` and
+    /// `examples`.
+    pub dir: PathBuf,
+    /// The selected profile, which has its own token, cache, inputs and
+    /// answers in `profiles/<name>`.
+    pub profile: Option<String>,
+    /// The names of all profiles, from the `profiles` directory and the config
+    /// file.
+    pub profiles: Vec<String>,
+    pub base_url: String,
+    pub token: Option<String>,
+    /// Minimum time between two downloads, also across processes.
+    pub fetch_interval: Duration,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    base_url: Option<String>,
+    token: Option<String>,
+    token_file: Option<PathBuf>,
+    fetch_interval: Option<f64>,
+    #[serde(default)]
+    profiles: BTreeMap<String, ProfileFile>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileFile {
+    token: Option<String>,
+    token_file: Option<PathBuf>,
+}
+
+/// The environment variables the settings are read from, usually
+/// [`Environment::current`]. Tests pass their own, so that they don't depend
+/// on the `AOC_*` variables of whoever runs them.
+#[derive(Debug, Clone, Default)]
+pub struct Environment(BTreeMap<OsString, OsString>);
+
+impl Environment {
+    /// The environment variables of this process.
+    pub fn current() -> Environment {
+        Environment(env::vars_os().collect())
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    fn var_os(&self, name: &str) -> Option<OsString> {
+        self.0.get(OsStr::new(name)).cloned()
+    }
+
+    /// A variable that is valid unicode, as with [`env::var`].
+    fn var(&self, name: &str) -> Option<String> {
+        self.var_os(name)?.into_string().ok()
+    }
+}
+
+/// Settings given on the command line, which override all other sources.
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    pub dir: Option<PathBuf>,
+    pub config: Option<PathBuf>,
+    pub profile: Option<String>,
+    pub base_url: Option<String>,
+    pub token_file: Option<PathBuf>,
+    pub fetch_interval: Option<Duration>,
+}
+
+impl Config {
+    pub fn load(overrides: &Overrides, env: &Environment) -> Result<Config> {
+        let dir = overrides
+            .dir
+            .clone()
+            .or_else(|| env.var_os("AOC_DIR").map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let config_path = overrides
+            .config
+            .clone()
+            .or_else(|| env.var_os("AOC_CONFIG").map(PathBuf::from));
+        let file = match &config_path {
+            Some(path) => read_config_file(path)?,
+            None if dir.join("aoc.json").exists() => read_config_file(&dir.join("aoc.json"))?,
+            None => ConfigFile::default(),
+        };
+
+        let base_url = overrides
+            .base_url
+            .clone()
+            .or_else(|| env.var("AOC_BASE_URL"))
+            .or(file.base_url)
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_owned());
+
+        let fetch_interval = match overrides.fetch_interval {
+            Some(interval) => interval,
+            None => match env.var("AOC_FETCH_INTERVAL") {
+                Some(secs) => parse_interval(&secs)?,
+                None => match file.fetch_interval {
+                    Some(secs) => Duration::try_from_secs_f64(secs)
+                        .map_err(|_| Error::InvalidConfig(format!("invalid fetch_interval {}", secs)))?,
+                    None => DEFAULT_FETCH_INTERVAL,
+                },
+            },
+        };
+
+        let mut profiles = file.profiles.keys().cloned().collect::<Vec<_>>();
+        if let Ok(entries) = std::fs::read_dir(dir.join("profiles")) {
+            for entry in entries {
+                let entry = entry?;
+                if entry.file_type()?.is_dir() {
+                    if let Some(name) = entry.file_name().to_str() {
+                        profiles.push(name.to_owned());
+                    }
+                }
+            }
+        }
+        profiles.sort();
+        profiles.dedup();
+
+        let profile = overrides.profile.clone().or_else(|| env.var("AOC_PROFILE"));
+        if let Some(profile) = &profile {
+            if !profiles.contains(profile) {
+                return Err(Error::InvalidConfig(format!(
+                    "profile {} does not exist, create profiles/{}/token.txt",
+                    profile, profile
+                )));
+            }
+        }
+
+        // An explicit token file takes precedence over a token from a lower
+        // priority source, and token.txt in the directory (of the profile) is
+        // the fallback. The token of the default user is never used for a
+        // profile.
+        let token_file = overrides
+            .token_file
+            .clone()
+            .or_else(|| env.var_os("AOC_TOKEN_FILE").map(PathBuf::from));
+        let token = match (token_file, &profile) {
+            (Some(path), _) => Some(std::fs::read_to_string(path)?),
+            (None, Some(profile)) => {
+                let profile_file = file.profiles.get(profile).cloned().unwrap_or_default();
+                let profile_dir = dir.join("profiles").join(profile);
+                match (profile_file.token, profile_file.token_file) {
+                    (Some(token), _) => Some(token),
+                    (None, Some(path)) => Some(std::fs::read_to_string(dir.join(path))?),
+                    (None, None) => std::fs::read_to_string(profile_dir.join("token.txt")).ok(),
+                }
+            }
+            (None, None) => {
+                let default_token_file = file.token_file.unwrap_or_else(|| PathBuf::from("token.txt"));
+                env.var("AOC_SESSION")
+                    .or(file.token)
+                    .or_else(|| std::fs::read_to_string(dir.join(default_token_file)).ok())
+            }
+        };
+
+        Ok(Config {
+            dir,
+            profile,
+            profiles,
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            token: token.map(|token| token.trim().to_owned()),
+            fetch_interval,
+        })
+    }
+
+    /// A file shared by all profiles.
+    pub fn path(&self, relative: impl AsRef<Path>) -> PathBuf {
+        self.dir.join(relative)
+    }
+
+    /// A file of the selected profile, in its own directory for a named
+    /// profile.
+    pub fn profile_path(&self, relative: impl AsRef<Path>) -> PathBuf {
+        match &self.profile {
+            Some(profile) => self.dir.join("profiles").join(profile).join(relative),
+            None => self.dir.join(relative),
+        }
+    }
+}
+
+fn read_config_file(path: &Path) -> Result<ConfigFile> {
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+/// Parses an interval given in (fractional) seconds.
+pub fn parse_interval(secs: &str) -> Result<Duration> {
+    match secs.trim().parse::<f64>().map(Duration::try_from_secs_f64) {
+        Ok(Ok(interval)) => Ok(interval),
+        _ => Err(Error::InvalidConfig(format!("invalid interval {}, expected seconds", secs))),
+    }
+}
+
+#[test]
+fn profile_test() {
+    let dir = crate::mock_server::temp_dir("profile");
+    std::fs::create_dir_all(dir.join("profiles/alice")).unwrap();
+    std::fs::write(dir.join("profiles/alice/token.txt"), "alice-token\n").unwrap();
+    std::fs::write(dir.join("aoc.json"), r#"{"profiles": {"bob": {"token": "bob-token"}}}"#).unwrap();
+    let overrides = |profile: &str| Overrides {
+        dir: Some(dir.clone()),
+        profile: Some(profile.to_owned()),
+        ..Overrides::default()
+    };
+    let env = Environment::default();
+
+    let alice = Config::load(&overrides("alice"), &env).unwrap();
+    assert_eq!(alice.token.as_deref(), Some("alice-token"));
+    assert_eq!(alice.profiles, ["alice", "bob"]);
+    assert_eq!(alice.profile_path("cache"), dir.join("profiles/alice/cache"));
+    assert_eq!(alice.path("last_fetch"), dir.join("last_fetch"));
+
+    let bob = Config::load(&overrides("bob"), &env).unwrap();
+    assert_eq!(bob.token.as_deref(), Some("bob-token"));
+    assert!(Config::load(&overrides("carol"), &env).is_err());
+
+    let mut env = Environment::default();
+    env.set("AOC_PROFILE", "bob");
+    let from_env = Config::load(&Overrides { profile: None, ..overrides("bob") }, &env).unwrap();
+    assert_eq!(from_env.profile.as_deref(), Some("bob"));
+}
+
+#[test]
+fn parse_interval_test() {
+    assert_eq!(parse_interval(" 1.5\n").unwrap(), Duration::from_millis(1500));
+    for secs in ["-1", "inf", "NaN", "1e30", "soon"] {
+        assert!(parse_interval(secs).is_err());
+    }
+}
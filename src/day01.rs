@@ -1,11 +1,13 @@
 day!(
     day01,
-    "https://adventofcode.com/2015/day/1/input",
+    1,
     part1,
     part2
 );
 
-fn part1(input: String) -> Result<isize> {
+use crate::generate::{InputGenerator, Rng};
+
+fn part1(input: &str) -> Result<isize> {
     Ok(input.chars().fold(0, |a, c| match c {
         '(' => a + 1,
         ')' => a - 1,
@@ -13,7 +15,7 @@ fn part1(input: String) -> Result<isize> {
     }))
 }
 
-fn part2(input: String) -> Result<usize> {
+fn part2(input: &str) -> Result<usize> {
     let mut floor = 0;
     for (index, c) in input.chars().enumerate() {
         match c {
@@ -23,7 +25,31 @@ fn part2(input: String) -> Result<usize> {
             _ => (),
         }
     }
-    Err(Error::Input("basement level not reached"))
+    Err(Error::input("basement level not reached"))
+}
+
+impl InputGenerator for Solver {
+    /// The number of parentheses.
+    const DEFAULT_SIZE: usize = 7000;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut parens = (0..size).map(|_| *rng.pick(&['(', ')'])).collect::<Vec<_>>();
+        // Part 2 needs the basement to be reached, so turn the last ups into
+        // downs until it is
+        let reaches_basement = |parens: &[char]| {
+            let mut floor = 0;
+            parens.iter().any(|&c| {
+                floor += if c == '(' { 1 } else { -1 };
+                floor < 0
+            })
+        };
+        while !reaches_basement(&parens) {
+            match parens.iter().rposition(|&c| c == '(') {
+                Some(idx) => parens[idx] = ')',
+                None => break,
+            }
+        }
+        parens.into_iter().collect()
+    }
 }
 
 #[test]
@@ -1,29 +1,32 @@
 day!(
     day02,
-    "https://adventofcode.com/2015/day/2/input",
+    2,
+    transform -> Vec<Dimension>,
     part1,
     part2
 );
 
+use crate::framework::{parse_lines, ParseOptions};
+use crate::generate::{InputGenerator, Rng};
+
 #[derive(Debug)]
-struct Dimension(usize, usize, usize);
-
-fn transform(input: String) -> Vec<Dimension> {
-    input
-        .split("\n")
-        .filter_map(|part| {
-            let mut iter = part.split('x');
-            let l = iter.next()?.parse().ok()?;
-            let w = iter.next()?.parse().ok()?;
-            let h = iter.next()?.parse().ok()?;
-            Some(Dimension(l, w, h))
-        })
-        .collect()
+pub struct Dimension(pub usize, pub usize, pub usize);
+
+pub fn transform(input: &str, options: &ParseOptions) -> Result<Vec<Dimension>> {
+    parse_lines(input, options, "expected LxWxH", |line| {
+        let mut iter = line.split('x');
+        let l = iter.next()?.parse().ok()?;
+        let w = iter.next()?.parse().ok()?;
+        let h = iter.next()?.parse().ok()?;
+        if iter.next().is_some() {
+            return None;
+        }
+        Some(Dimension(l, w, h))
+    })
 }
 
-fn part1(input: String) -> Result<usize> {
-    let input = transform(input);
-    Ok(input
+fn part1(dimensions: &[Dimension]) -> Result<usize> {
+    Ok(dimensions
         .iter()
         .map(|Dimension(l, w, h)| {
             let a = l * w;
@@ -35,9 +38,8 @@ fn part1(input: String) -> Result<usize> {
         .sum())
 }
 
-fn part2(input: String) -> Result<usize> {
-    let input = transform(input);
-    Ok(input
+fn part2(dimensions: &[Dimension]) -> Result<usize> {
+    Ok(dimensions
         .iter()
         .map(|Dimension(l, w, h)| {
             let a = l + w;
@@ -49,15 +51,26 @@ fn part2(input: String) -> Result<usize> {
         .sum())
 }
 
+impl InputGenerator for Solver {
+    /// The number of presents.
+    const DEFAULT_SIZE: usize = 1000;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        rng.lines(size, |rng| format!("{}x{}x{}", rng.range(1..31), rng.range(1..31), rng.range(1..31)))
+    }
+}
+
 #[test]
 fn day02_test() {
-    assert_results!(part1,
+    let parse = |input: &str| transform(input, &ParseOptions::default());
+    assert_results!(parse, part1,
         "2x3x4"  => 58,
         "1x1x10" => 43,
     );
     
-    assert_results!(part2,
+    assert_results!(parse, part2,
         "2x3x4"  => 34,
         "1x1x10" => 14,
     );
+
+    assert!(parse("2x3x4\n2x3").is_err());
 }
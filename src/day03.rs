@@ -1,14 +1,16 @@
 day!(
     day03,
-    "https://adventofcode.com/2015/day/3/input",
+    3,
+    transform -> Vec<Direction>,
     part1,
     part2
 );
 
+use crate::generate::{InputGenerator, Rng};
 use std::collections::HashSet;
 
 #[derive(Debug)]
-enum Direction {
+pub enum Direction {
     West,
     East,
     South,
@@ -33,9 +35,9 @@ impl Direction {
     }
 }
 
-fn transform(input: String) -> Vec<Direction> {
+pub fn transform(input: &str) -> Result<Vec<Direction>> {
     use self::Direction::*;
-    input
+    Ok(input
         .chars()
         .filter_map(|c| match c {
             '<' => Some(West),
@@ -44,27 +46,25 @@ fn transform(input: String) -> Vec<Direction> {
             '^' => Some(North),
             _ => None,
         })
-        .collect()
+        .collect())
 }
 
-fn part1(input: String) -> Result<usize> {
-    let input = transform(input);
+fn part1(input: &[Direction]) -> Result<usize> {
     let mut pos = (0, 0);
     let mut visited = HashSet::new();
     visited.insert(pos);
-    for direction in &input {
+    for direction in input {
         direction.apply(&mut pos);
         visited.insert(pos);
     }
     Ok(visited.len())
 }
 
-fn part2(input: String) -> Result<usize> {
-    let input = transform(input);
+fn part2(input: &[Direction]) -> Result<usize> {
     let (mut posa, mut posb) = ((0, 0), (0, 0));
     let mut visited = HashSet::new();
     visited.insert(posa);
-    for direction in &input {
+    for direction in input {
         direction.apply(&mut posa);
         visited.insert(posa);
         ::std::mem::swap(&mut posa, &mut posb);
@@ -72,14 +72,22 @@ fn part2(input: String) -> Result<usize> {
     Ok(visited.len())
 }
 
+impl InputGenerator for Solver {
+    /// The number of moves.
+    const DEFAULT_SIZE: usize = 8192;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size).map(|_| *rng.pick(&['^', 'v', '<', '>'])).collect()
+    }
+}
+
 #[test]
 fn day03_test() {
-    assert_results!(part1,
+    assert_results!(transform, part1,
         ">"          => 2,
         "^>v<"       => 4,
         "^v^v^v^v^v" => 2,
     );
-    assert_results!(part2,
+    assert_results!(transform, part2,
         "^v"         => 3,
         "^>v<"       => 3,
         "^v^v^v^v^v" => 11,
@@ -1,16 +1,22 @@
 day!(
     day04,
-    "https://adventofcode.com/2015/day/4/input",
+    4,
     part1,
-    part2
+    part2,
+    timeout: 120
 );
 
-fn part1(prefix: String) -> Result<usize> {
+use crate::generate::{InputGenerator, Rng};
+
+fn part1(prefix: &str) -> Result<usize> {
     use crypto::{digest::Digest, md5::Md5};
     let mut hasher = Md5::new();
     let key = prefix.as_bytes();
 
     for i in 0.. {
+        if i % 4096 == 0 {
+            crate::framework::check_cancelled()?;
+        }
         hasher.input(&key);
         hasher.input(&i.to_string().as_bytes());
         if &hasher.result_str()[0..5] == "00000" {
@@ -21,12 +27,15 @@ fn part1(prefix: String) -> Result<usize> {
 
     unreachable!();
 }
-fn part2(prefix: String) -> Result<usize> {
+fn part2(prefix: &str) -> Result<usize> {
     use crypto::{digest::Digest, md5::Md5};
     let mut hasher = Md5::new();
     let key = prefix.as_bytes();
 
     for i in 0.. {
+        if i % 4096 == 0 {
+            crate::framework::check_cancelled()?;
+        }
         hasher.input(&key);
         hasher.input(&i.to_string().as_bytes());
         if &hasher.result_str()[0..6] == "000000" {
@@ -38,6 +47,14 @@ fn part2(prefix: String) -> Result<usize> {
     unreachable!();
 }
 
+impl InputGenerator for Solver {
+    /// The length of the secret key.
+    const DEFAULT_SIZE: usize = 8;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size).map(|_| rng.lowercase()).collect()
+    }
+}
+
 #[test]
 #[cfg_attr(debug_assertions, ignore)]
 fn day04_test() {
@@ -1,11 +1,13 @@
 day!(
     day05,
-    "https://adventofcode.com/2015/day/5/input",
+    5,
     part1,
     part2
 );
 
-fn part1(input: String) -> Result<usize> {
+use crate::generate::{InputGenerator, Rng};
+
+fn part1(input: &str) -> Result<usize> {
     fn is_nice(input: &str) -> bool {
         let input = input.as_bytes();
         let vowel_count = input
@@ -40,7 +42,7 @@ fn part1(input: String) -> Result<usize> {
     Ok(input.split('\n').filter(|x| is_nice(x)).count())
 }
 
-fn part2(input: String) -> Result<usize> {
+fn part2(input: &str) -> Result<usize> {
     fn is_nice(input: &str) -> bool {
         let input = input.as_bytes();
         if !(0..input.len() - 2)
@@ -61,6 +63,14 @@ fn part2(input: String) -> Result<usize> {
     Ok(input.split('\n').filter(|x| is_nice(x)).count())
 }
 
+impl InputGenerator for Solver {
+    /// The number of strings.
+    const DEFAULT_SIZE: usize = 1000;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        rng.lines(size, |rng| (0..16).map(|_| rng.lowercase()).collect())
+    }
+}
+
 #[test]
 fn day05_test() {
     assert_results!(part1,
@@ -1,20 +1,23 @@
 day!(
     day06,
-    "https://adventofcode.com/2015/day/6/input",
+    6,
+    transform -> Vec<Instruction>,
     part1,
     part2
 );
 
+use crate::framework::{try_parse_lines, ParseOptions};
+use crate::generate::{InputGenerator, Rng};
 use std::str::FromStr;
 use std::num::ParseIntError;
 
-struct Point {
-    x: usize,
-    y: usize,
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-enum ParsePointErr {
+pub enum ParsePointErr {
     EndOfInput,
     RemainingInput,
     ParseInt(ParseIntError),
@@ -40,51 +43,50 @@ impl FromStr for Point {
     }
 }
 
-enum Action {
+pub enum Action {
     TurnOn,
     TurnOff,
     Toggle,
 }
 
-struct Instruction {
-    action: Action,
-    from: Point,
-    to: Point,
+pub struct Instruction {
+    pub action: Action,
+    pub from: Point,
+    pub to: Point,
 }
 
-fn transform(input: String) -> Vec<Instruction> {
+pub fn transform(input: &str, options: &ParseOptions) -> Result<Vec<Instruction>> {
     use self::Action::*;
-    input
-        .split("\n")
-        .filter_map(|line| {
-            let (action, remainder) = if line.starts_with("turn on ") {
-                (TurnOn, &line[8..])
-            } else if line.starts_with("turn off ") {
-                (TurnOff, &line[9..])
-            } else if line.starts_with("toggle ") {
-                (Toggle, &line[7..])
-            } else {
-                return None;
-            };
-            let mut iter = remainder.split(' ');
-            let from = iter.next()?;
-            if iter.next()? != "through" {
-                return None;
-            }
-            let to = iter.next()?;
-            if let Some(_) = iter.next() {
-                return None;
-            }
-            Some(Instruction {
-                action,
-                from: from.parse().ok()?,
-                to: to.parse().ok()?,
-            })
-        })
-        .collect()
+    try_parse_lines(input, options, |idx, line| {
+        let (action, remainder) = if let Some(remainder) = line.strip_prefix("turn on ") {
+            (TurnOn, remainder)
+        } else if let Some(remainder) = line.strip_prefix("turn off ") {
+            (TurnOff, remainder)
+        } else if let Some(remainder) = line.strip_prefix("toggle ") {
+            (Toggle, remainder)
+        } else {
+            return Err(Error::input("expected turn on, turn off or toggle").at(idx, line, line));
+        };
+        let point = |s: &str| match s.parse::<Point>() {
+            Ok(point) if point.x < 1000 && point.y < 1000 => Ok(point),
+            Ok(_) => Err(Error::input("point outside of the 1000x1000 grid").at(idx, line, s)),
+            Err(_) => Err(Error::input("invalid point, expected x,y").at(idx, line, s)),
+        };
+        let mut iter = remainder.split(' ');
+        let from = point(iter.next().unwrap())?;
+        match iter.next() {
+            Some("through") => {}
+            Some(s) => return Err(Error::input("expected 'through'").at(idx, line, s)),
+            None => return Err(Error::input("expected 'through'").at(idx, line, &line[line.len()..])),
+        }
+        let to = point(iter.next().unwrap_or(&line[line.len()..]))?;
+        if let Some(s) = iter.next() {
+            return Err(Error::input("unexpected text after instruction").at(idx, line, s));
+        }
+        Ok(Instruction { action, from, to })
+    })
 }
-fn part1(instructions: String) -> Result<usize> {
-    let instructions = transform(instructions);
+fn part1(instructions: &[Instruction]) -> Result<usize> {
     let mut grid = vec![[false; 1000]; 1000];
     for instruction in instructions {
         for x in instruction.from.x..(instruction.to.x + 1) {
@@ -101,8 +103,7 @@ fn part1(instructions: String) -> Result<usize> {
     Ok(grid.iter().flat_map(|x| x.iter()).filter(|&&x| x).count())
 }
 
-fn part2(instructions: String) -> Result<usize> {
-    let instructions = transform(instructions);
+fn part2(instructions: &[Instruction]) -> Result<usize> {
     let mut grid = vec![[0; 1000]; 1000];
     for instruction in instructions {
         for x in instruction.from.x..(instruction.to.x + 1) {
@@ -120,16 +121,48 @@ fn part2(instructions: String) -> Result<usize> {
     Ok(grid.iter().flat_map(|x| x.iter()).sum())
 }
 
+impl InputGenerator for Solver {
+    /// The number of instructions.
+    const DEFAULT_SIZE: usize = 300;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        rng.lines(size, |rng| {
+            let action = *rng.pick(&["turn on", "turn off", "toggle"]);
+            let (x1, x2) = (rng.below(1000), rng.below(1000));
+            let (y1, y2) = (rng.below(1000), rng.below(1000));
+            format!(
+                "{} {},{} through {},{}",
+                action,
+                x1.min(x2),
+                y1.min(y2),
+                x1.max(x2),
+                y1.max(y2)
+            )
+        })
+    }
+}
+
 #[test]
 fn day06_test() {
-    assert_results!(part1,
+    let parse = |input: &str| transform(input, &ParseOptions::default());
+    assert_results!(parse, part1,
         "turn on 0,0 through 999,999" => 1000000,
         "toggle 0,0 through 999,0"    => 1000,
         "turn on 0,0 through 999,999\nturn off 499,499 through 500,500" => 999996,
     );
 
-    assert_results!(part2,
+    assert_results!(parse, part2,
         "turn on 0,0 through 0,0"    => 1,
         "toggle 0,0 through 999,999" => 2000000,
     );
+
+    assert_eq!(
+        parse("toggle 0,0 through 999,0\nturn on 2,x through 3,3")
+            .err()
+            .unwrap()
+            .to_string(),
+        "invalid input: invalid point, expected x,y at line 2, column 9
+    2 | turn on 2,x through 3,3
+      |         ^"
+    );
+    assert!(parse("turn on 0,0 through 999,1000").is_err());
 }
@@ -1,25 +1,28 @@
 day!(
     day07,
-    "https://adventofcode.com/2015/day/7/input",
+    7,
+    transform -> Vec<Instruction>,
     part1,
     part2
 );
 
+use crate::framework::{parse_lines, ParseOptions};
+use crate::generate::{InputGenerator, Rng};
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::str::FromStr;
 
-type Value = u16;
-type Ident = String;
+pub type Value = u16;
+pub type Ident = String;
 
 #[derive(Debug, Clone)]
-struct Instruction {
-    target: Ident,
-    action: Action,
+pub struct Instruction {
+    pub target: Ident,
+    pub action: Action,
 }
 
 #[derive(Debug, Clone)]
-enum Action {
+pub enum Action {
     Set(Operand),
     Not(Operand),
     And(Operand, Operand),
@@ -29,7 +32,7 @@ enum Action {
 }
 
 #[derive(Debug, Clone)]
-enum Operand {
+pub enum Operand {
     Value(Value),
     Ident(Ident),
 }
@@ -46,46 +49,49 @@ impl FromStr for Operand {
     }
 }
 
-fn transform(input: String) -> Vec<Instruction> {
+pub fn transform(input: &str, options: &ParseOptions) -> Result<Vec<Instruction>> {
     use self::Action::*;
-    input
-        .split("\n")
-        .filter_map(|line| {
-            let pivot = line.find(" -> ")?;
-            let target = line.get(pivot + 4..)?.to_string();
-            let action = line.get(0..pivot)?;
-            let action = if action.starts_with("NOT ") {
-                Not(action.get(4..)?.parse().ok()?)
-            } else if let None = action.find(" ") {
-                Set(action.parse().ok()?)
-            } else {
-                let mut iter = action.split(" ");
-                let ident = iter.next()?.to_string();
-                match iter.next()? {
-                    "AND" => And(ident.parse().ok()?, iter.next()?.parse().ok()?),
-                    "OR" => Or(ident.parse().ok()?, iter.next()?.parse().ok()?),
-                    "LSHIFT" => LShift(ident.parse().ok()?, iter.next()?.parse().ok()?),
-                    "RSHIFT" => RShift(ident.parse().ok()?, iter.next()?.parse().ok()?),
-                    _ => return None,
-                }
+    parse_lines(input, options, "expected an instruction such as 'x AND y -> z'", |line| {
+        let pivot = line.find(" -> ")?;
+        let target = line.get(pivot + 4..)?.to_string();
+        let action = line.get(0..pivot)?;
+        let action = if action.starts_with("NOT ") {
+            Not(action.get(4..)?.parse().ok()?)
+        } else if !action.contains(' ') {
+            Set(action.parse().ok()?)
+        } else {
+            let mut iter = action.split(" ");
+            let ident = iter.next()?.to_string();
+            let action = match iter.next()? {
+                "AND" => And(ident.parse().ok()?, iter.next()?.parse().ok()?),
+                "OR" => Or(ident.parse().ok()?, iter.next()?.parse().ok()?),
+                "LSHIFT" => LShift(ident.parse().ok()?, iter.next()?.parse().ok()?),
+                "RSHIFT" => RShift(ident.parse().ok()?, iter.next()?.parse().ok()?),
+                _ => return None,
             };
+            if iter.next().is_some() {
+                return None;
+            }
+            action
+        };
 
-            Some(Instruction { target, action })
-        })
-        .collect()
+        Some(Instruction { target, action })
+    })
 }
 
-fn part1(instructions: String) -> Result<Value> {
-    let ref instructions = transform(instructions);
-    type Instructions<'a> = HashMap<&'a str, &'a Instruction>;
-    type Values<'a> = HashMap<&'a str, Value>;
+type Instructions<'a> = HashMap<&'a str, &'a Instruction>;
+type Values<'a> = HashMap<&'a str, Value>;
 
+/// Evaluates the circuit, returning the signal on every wire. The wires in
+/// `overrides` carry the given signal instead of the one from their
+/// instruction.
+pub fn evaluate<'a>(instructions: &'a [Instruction], overrides: &[(&'a str, Value)]) -> HashMap<String, Value> {
     let instructions: Instructions = instructions
         .iter()
         .map(|x| (x.target.as_str(), x))
         .collect();
 
-    let mut values: Values = HashMap::new();
+    let mut values: Values = overrides.iter().cloned().collect();
 
     fn value_of<'a>(
         ident: &'a str,
@@ -124,105 +130,99 @@ fn part1(instructions: String) -> Result<Value> {
         value_of(ident, &instructions, &mut values);
     }
 
-    values.get("a").cloned().ok_or(Error::Input("no wire 'a'"))
-    // // For unit test:
-    // Ok(values
-    //     .iter()
-    //     .sorted()
-    //     .into_iter()
-    //     .map(|(name, value)| format!("{}: {}", name, value))
-    //     .take(8)
-    //     .join("\n"))
+    values
+        .into_iter()
+        .map(|(ident, value)| (ident.to_owned(), value))
+        .collect()
 }
 
-fn part2(instructions: String) -> Result<Value> {
-    let ref instructions = transform(instructions);
-
-    type Instructions<'a> = HashMap<&'a str, &'a Instruction>;
-    type Values<'a> = HashMap<&'a str, Value>;
-
-    let instructions: Instructions = instructions
-        .iter()
-        .map(|x| (x.target.as_str(), x))
-        .collect();
+fn part1(instructions: &[Instruction]) -> Result<Value> {
+    evaluate(instructions, &[])
+        .get("a")
+        .cloned()
+        .ok_or(Error::input("no wire 'a'"))
+}
 
-    let mut values: Values = HashMap::new();
+fn part2(instructions: &[Instruction]) -> Result<Value> {
+    let a = part1(instructions)?;
+    evaluate(instructions, &[("b", a)])
+        .get("a")
+        .cloned()
+        .ok_or(Error::input("no wire 'a'"))
+}
 
-    fn value_of<'a>(
-        ident: &'a str,
-        instructions: &Instructions<'a>,
-        values: &mut Values<'a>,
-    ) -> Option<Value> {
-        if let Some(&value) = values.get(ident) {
-            return Some(value);
+/// The name of the nth wire: a, b, ..., z, aa, ab, ...
+fn wire_name(mut n: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'a' + (n % 26) as u8);
+        if n < 26 {
+            break;
         }
+        n = n / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
 
-        let instruction = instructions.get(ident)?;
-        macro_rules! resolve {
-            ($ident:expr) => {
-                match $ident {
-                    Operand::Value(value) => *value,
-                    Operand::Ident(ident) => value_of(ident.as_str(), instructions, values)?,
-                }
+impl InputGenerator for Solver {
+    /// The number of wires.
+    const DEFAULT_SIZE: usize = 339;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // Every wire only reads the wires before it, so the circuit is
+        // acyclic. Wire b comes first and is set to a signal, so that part 2
+        // can override it, and wire a comes last.
+        let mut wires = vec!["b".to_owned()];
+        wires.extend((2..size.max(2)).map(wire_name));
+        wires.push("a".to_owned());
+
+        let mut lines = vec![format!("{} -> b", rng.below(65536))];
+        for (i, wire) in wires.iter().enumerate().skip(1) {
+            let inputs = &wires[..i];
+            let action = match rng.below(10) {
+                0 => rng.below(65536).to_string(),
+                1 => rng.pick(inputs).clone(),
+                2 | 3 => format!("NOT {}", rng.pick(inputs)),
+                4 | 5 => format!("{} AND {}", rng.pick(inputs), rng.pick(inputs)),
+                6 | 7 => format!("{} OR {}", rng.pick(inputs), rng.pick(inputs)),
+                8 => format!("{} LSHIFT {}", rng.pick(inputs), rng.range(1..4)),
+                _ => format!("{} RSHIFT {}", rng.pick(inputs), rng.range(1..4)),
             };
+            lines.push(format!("{} -> {}", action, wire));
         }
-
-        use self::Action::*;
-        let value = match &instruction.action {
-            Set(a) => resolve!(a),
-            Not(a) => !resolve!(a),
-            And(a, b) => resolve!(a) & resolve!(b),
-            Or(a, b) => resolve!(a) | resolve!(b),
-            LShift(a, b) => resolve!(a) << b,
-            RShift(a, b) => resolve!(a) >> b,
-        };
-
-        values.insert(ident, value);
-        Some(value)
-    }
-
-    for &ident in instructions.keys() {
-        value_of(ident, &instructions, &mut values);
-    }
-
-    {
-        let v = values[&"a"];
-        values.clear();
-        values.insert(&"b", v);
+        rng.shuffle(&mut lines);
+        lines.join("\n")
     }
+}
 
-    for &ident in instructions.keys() {
-        value_of(ident, &instructions, &mut values);
+#[test]
+fn day07_test() {
+    let instructions = transform(
+        "123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i",
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    let values = evaluate(&instructions, &[]);
+    let expected = [
+        ("d", 72),
+        ("e", 507),
+        ("f", 492),
+        ("g", 114),
+        ("h", 65412),
+        ("i", 65079),
+        ("x", 123),
+        ("y", 456),
+    ];
+    assert_eq!(values.len(), expected.len());
+    for &(ident, value) in &expected {
+        assert_eq!(values[ident], value);
     }
-
-    values.get("a").cloned().ok_or(Error::Input("no wire 'a'"))
-    // // For unit test:
-    // Ok(values
-    //     .iter()
-    //     .sorted()
-    //     .into_iter()
-    //     .map(|(name, value)| format!("{}: {}", name, value))
-    //     .take(8)
-    //     .join("\n"))
+    assert_eq!(evaluate(&instructions, &[("x", 1)])["d"], 0);
 }
-
-// #[test]
-// fn day07_test() {
-//     assert_results!(part1,
-// "123 -> x
-// 456 -> y
-// x AND y -> d
-// x OR y -> e
-// x LSHIFT 2 -> f
-// y RSHIFT 2 -> g
-// NOT x -> h
-// NOT y -> i" => "d: 72
-// e: 507
-// f: 492
-// g: 114
-// h: 65412
-// i: 65079
-// x: 123
-// y: 456"
-//     );
-// }
@@ -1,11 +1,13 @@
 day!(
     day08,
-    "https://adventofcode.com/2015/day/8/input",
+    8,
     part1,
     part2
 );
 
-fn part1(input: String) -> Result<usize> {
+use crate::generate::{InputGenerator, Rng};
+
+fn part1(input: &str) -> Result<usize> {
     Ok(input
         .split('\n')
         .filter(|line| line.len() >= 2)
@@ -70,7 +72,7 @@ fn part1(input: String) -> Result<usize> {
         .sum())
 }
 
-fn part2(input: String) -> Result<usize> {
+fn part2(input: &str) -> Result<usize> {
     Ok(input
         .split('\n')
         .filter(|line| line.len() >= 2)
@@ -81,6 +83,26 @@ fn part2(input: String) -> Result<usize> {
         .sum())
 }
 
+impl InputGenerator for Solver {
+    /// The number of strings.
+    const DEFAULT_SIZE: usize = 300;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        rng.lines(size, |rng| {
+            let mut line = String::from("\"");
+            for _ in 0..rng.below(30) {
+                match rng.below(10) {
+                    0 => line.push_str("\\\\"),
+                    1 => line.push_str("\\\""),
+                    2 => line.push_str(&format!("\\x{:02x}", rng.below(256))),
+                    _ => line.push(rng.lowercase()),
+                }
+            }
+            line.push('"');
+            line
+        })
+    }
+}
+
 #[test]
 fn day08_test() {
     assert_results!(part1,
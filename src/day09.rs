@@ -1,23 +1,27 @@
 day!(
     day09,
-    "https://adventofcode.com/2015/day/9/input",
+    9,
+    transform -> (Place, Routes),
     part1,
     part2
 );
 
+use crate::framework::{parse_lines, ParseOptions};
+use crate::generate::{InputGenerator, Rng};
 use permutohedron::Heap;
 use std::collections::HashMap;
 
-type Place = u8;
+pub type Place = u8;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
-struct Connection {
+/// A route between two places, in either direction.
+pub struct Connection {
     a: Place,
     b: Place,
 }
 
 impl Connection {
-    fn new(a: Place, b: Place) -> Connection {
+    pub fn new(a: Place, b: Place) -> Connection {
         if a <= b {
             Connection { a, b }
         } else {
@@ -26,31 +30,29 @@ impl Connection {
     }
 }
 
-type Routes = HashMap<Connection, usize>;
+pub type Routes = HashMap<Connection, usize>;
 
-fn transform(input: String) -> (Place, Routes) {
+pub fn transform(input: &str, options: &ParseOptions) -> Result<(Place, Routes)> {
     let mut place_names = HashMap::new();
-    let results = input
-        .split('\n')
-        .filter_map(|line| {
-            let to_idx = line.find(" to ")?;
-            let eq_idx = line.find(" = ")?;
-            let fr = &line[0..to_idx];
-            let to = &line[to_idx + 4..eq_idx];
+    let results = parse_lines(input, options, "expected 'A to B = distance'", |line| {
+        let to_idx = line.find(" to ")?;
+        let eq_idx = line.find(" = ")?;
+        let fr = line.get(0..to_idx)?;
+        let to = line.get(to_idx + 4..eq_idx)?;
+        let dist: usize = line[eq_idx + 3..].parse().ok()?;
 
-            let l = place_names.len() as Place;
-            let fr: Place = *place_names.entry(fr).or_insert(l);
-            let l = place_names.len() as Place;
-            let to: Place = *place_names.entry(to).or_insert(l);
+        let l = place_names.len() as Place;
+        let fr: Place = *place_names.entry(fr).or_insert(l);
+        let l = place_names.len() as Place;
+        let to: Place = *place_names.entry(to).or_insert(l);
 
-            let dist: usize = line[eq_idx + 3..].parse().ok()?;
-            Some((Connection::new(fr, to), dist))
-        })
-        .collect();
-    (place_names.len() as Place, results)
+        Some((Connection::new(fr, to), dist))
+    })?;
+    Ok((place_names.len() as Place, results.into_iter().collect()))
 }
 
-fn route_lengths((place_count, routes): (Place, Routes)) -> Vec<usize> {
+/// The lengths of every route that visits all places once.
+pub fn route_lengths(&(place_count, ref routes): &(Place, Routes)) -> Vec<usize> {
     let mut data = (0..place_count).collect::<Vec<_>>();
     let heap = Heap::new(&mut data);
     heap.map(|permutation| {
@@ -68,25 +70,42 @@ fn route_lengths((place_count, routes): (Place, Routes)) -> Vec<usize> {
     .collect()
 }
 
-fn part1(data: String) -> Result<usize> {
-    let data: (Place, Routes) = transform(data);
+fn part1(data: &(Place, Routes)) -> Result<usize> {
     Ok(*route_lengths(data).iter().min().unwrap())
 }
 
-fn part2(data: String) -> Result<usize> {
-    let data: (Place, Routes) = transform(data);
+fn part2(data: &(Place, Routes)) -> Result<usize> {
     Ok(*route_lengths(data).iter().max().unwrap())
 }
 
+impl InputGenerator for Solver {
+    /// The number of places.
+    const DEFAULT_SIZE: usize = 8;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut names = Vec::new();
+        for _ in 0..size {
+            rng.name(&mut names);
+        }
+        let mut lines = Vec::new();
+        for (i, from) in names.iter().enumerate() {
+            for to in &names[i + 1..] {
+                lines.push(format!("{} to {} = {}", from, to, rng.range(10..150)));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
 #[test]
 fn day09_test() {
-    assert_results!(part1,
+    let parse = |input: &str| transform(input, &ParseOptions::default());
+    assert_results!(parse, part1,
 "London to Dublin = 464
 London to Belfast = 518
 Dublin to Belfast = 141"
 => 605
     );
-    assert_results!(part2,
+    assert_results!(parse, part2,
 "London to Dublin = 464
 London to Belfast = 518
 Dublin to Belfast = 141"
@@ -1,23 +1,31 @@
 day!(
     day10,
-    "https://adventofcode.com/2015/day/10/input",
+    10,
     part1,
-    part2
+    part2,
+    params: {
+        /// How many times part 1 applies look-and-say.
+        part1_iterations: usize = 40,
+        /// How many times part 2 applies look-and-say.
+        part2_iterations: usize = 50,
+    }
 );
 
-fn apply_n(input: String, times: usize) -> Result<usize> {
-    let mut s = input;
+use crate::generate::{InputGenerator, Rng};
+
+fn apply_n(input: &str, times: usize) -> Result<usize> {
+    let mut s = input.to_owned();
     for _ in 0..times {
         s = look_and_say(&s)?;
     }
     Ok(s.len())
 }
 
-fn part1(input: String) -> Result<usize> {
-    apply_n(input, 40)
+fn part1(input: &str, params: &Params) -> Result<usize> {
+    apply_n(input, params.part1_iterations)
 }
-fn part2(input: String) -> Result<usize> {
-    apply_n(input, 50)
+fn part2(input: &str, params: &Params) -> Result<usize> {
+    apply_n(input, params.part2_iterations)
 }
 
 fn look_and_say(input: &str) -> Result<String> {
@@ -26,7 +34,7 @@ fn look_and_say(input: &str) -> Result<String> {
     let mut chars = input.chars();
     let mut curr = chars
         .next()
-        .ok_or(Error::Input("look_and_say requires at least 1 character"))?;
+        .ok_or(Error::input("look_and_say requires at least 1 character"))?;
     let mut count = 1;
     while let Some(next) = chars.next() {
         if next == curr {
@@ -41,6 +49,14 @@ fn look_and_say(input: &str) -> Result<String> {
     Ok(result)
 }
 
+impl InputGenerator for Solver {
+    /// The number of digits.
+    const DEFAULT_SIZE: usize = 10;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size).map(|_| *rng.pick(&['1', '2', '3'])).collect()
+    }
+}
+
 #[test]
 fn day10_test() {
     assert_eq!("11", look_and_say("1").unwrap());
@@ -1,10 +1,13 @@
 day!(
     day11,
-    "https://adventofcode.com/2015/day/11/input",
+    11,
+    validate_input -> Vec<u8>,
     part1,
     part2
 );
 
+use crate::generate::{InputGenerator, Rng};
+
 fn rule_abc(pw: &[u8]) -> bool {
     if pw.len() < 3 {
         return false;
@@ -53,35 +56,50 @@ fn cycle_password(pw: &mut [u8]) {
     cycle_char(pw.len() - 1, pw);
 }
 
-fn cycle_password_until_valid(pw: &mut [u8]) {
+/// Increments the password until it satisfies all rules.
+pub fn cycle_password_until_valid(pw: &mut [u8]) {
     cycle_password(pw);
     while !rule_abc(pw) || !rule_iol(pw) || !rule_two_pairs(pw) {
         cycle_password(pw);
     }
 }
 
-fn validate_input(input: String) -> Result<Vec<u8>> {
-    let pw = input.into_bytes();
+pub fn validate_input(input: &str) -> Result<Vec<u8>> {
+    let pw = input.as_bytes().to_vec();
     for &c in &pw {
         if c < b'a' || c > b'z' {
-            return Err(Error::Input("invalid input in puzzle"));
+            return Err(Error::input("invalid input in puzzle"));
         }
     }
     Ok(pw)
 }
 
-fn part1(input: String) -> Result<String> {
-    let mut pw = validate_input(input)?;
+fn part1(pw: &[u8]) -> Result<String> {
+    let mut pw = pw.to_vec();
     cycle_password_until_valid(&mut pw);
     Ok(unsafe { String::from_utf8_unchecked(pw) })
 }
-fn part2(input: String) -> Result<String> {
-    let mut pw = validate_input(input)?;
+fn part2(pw: &[u8]) -> Result<String> {
+    let mut pw = pw.to_vec();
     cycle_password_until_valid(&mut pw);
     cycle_password_until_valid(&mut pw);
     Ok(unsafe { String::from_utf8_unchecked(pw) })
 }
 
+impl InputGenerator for Solver {
+    /// The length of the password, at least 8 so that there are valid
+    /// passwords to find.
+    const DEFAULT_SIZE: usize = 8;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // Starting below z leaves valid passwords such as zzabcdda before
+        // the increments run out of letters
+        let first = (b'a' + rng.below(25) as u8) as char;
+        std::iter::once(first)
+            .chain((1..size.max(8)).map(|_| rng.lowercase()))
+            .collect()
+    }
+}
+
 #[test]
 fn day11_test() {
     assert!(rule_abc(b"hijklmmn"));
@@ -1,10 +1,11 @@
 day!(
     day12,
-    "https://adventofcode.com/2015/day/12/input",
+    12,
     part1,
     part2
 );
 
+use crate::generate::{InputGenerator, Rng};
 use itertools::Itertools;
 
 fn extract_numbers<'a>(input: &'a str) -> impl Iterator<Item = isize> + 'a {
@@ -41,13 +42,13 @@ fn extract_numbers<'a>(input: &'a str) -> impl Iterator<Item = isize> + 'a {
     })
 }
 
-fn part1(input: String) -> Result<isize> {
-    Ok(extract_numbers(&input).sum())
+fn part1(input: &str) -> Result<isize> {
+    Ok(extract_numbers(input).sum())
 }
 
-fn part2(input: String) -> Result<isize> {
+fn part2(input: &str) -> Result<isize> {
     use serde_json::Value;
-    let mut json = serde_json::from_str(input.as_str())?;
+    let mut json = serde_json::from_str(input)?;
     fn prune_red(json: &mut Value) {
         match json {
             Value::Object(map) => {
@@ -73,13 +74,57 @@ fn part2(input: String) -> Result<isize> {
     }
 
     prune_red(&mut json);
-    part1(serde_json::to_string(&json)?)
+    part1(&serde_json::to_string(&json)?)
+}
+
+/// A JSON value that uses up to `budget` values, nested at most 6 deep.
+fn generate_value(rng: &mut Rng, budget: &mut usize, depth: usize) -> String {
+    *budget = budget.saturating_sub(1);
+    let kinds = if depth < 6 && *budget > 0 { 4 } else { 2 };
+    match rng.below(kinds) {
+        0 => (rng.below(400) as isize - 200).to_string(),
+        1 => format!("\"{}\"", rng.pick(&["red", "green", "blue", "orange", "yellow", "violet"])),
+        2 => {
+            let mut items = Vec::new();
+            for _ in 0..rng.range(1..6) {
+                if *budget == 0 {
+                    break;
+                }
+                items.push(generate_value(rng, budget, depth + 1));
+            }
+            format!("[{}]", items.join(","))
+        }
+        _ => {
+            let mut fields = Vec::new();
+            for key in 0..rng.range(1..6) {
+                if *budget == 0 {
+                    break;
+                }
+                let value = generate_value(rng, budget, depth + 1);
+                fields.push(format!("\"{}\":{}", (b'a' + key as u8) as char, value));
+            }
+            format!("{{{}}}", fields.join(","))
+        }
+    }
+}
+
+impl InputGenerator for Solver {
+    /// The number of values in the document.
+    const DEFAULT_SIZE: usize = 2000;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut budget = size;
+        let mut values = Vec::new();
+        while budget > 0 {
+            values.push(generate_value(rng, &mut budget, 1));
+        }
+        format!("[{}]", values.join(","))
+    }
 }
 
 #[test]
 fn day12_test() {
-    fn extract(input: String) -> Result<&'static [isize]> {
-        let res: Box<Vec<isize>> = Box::new(extract_numbers(&input).collect());
+    fn extract(input: &str) -> Result<&'static [isize]> {
+        let res: Box<Vec<isize>> = Box::new(extract_numbers(input).collect());
         Ok(Box::leak(res).as_slice())
     }
     assert_results!(extract,
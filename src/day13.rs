@@ -1,49 +1,58 @@
 day!(
     day13,
-    "https://adventofcode.com/2015/day/13/input",
+    13,
+    parse_happiness -> (People, Happiness),
     part1,
     part2
 );
 
+use crate::framework::{parse_lines, ParseOptions};
+use crate::generate::{InputGenerator, Rng};
 use permutohedron::Heap;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
-type People<'a> = HashSet<&'a str>;
-type Happiness<'a> = HashMap<(&'a str, &'a str), isize>;
+pub type People = HashSet<String>;
+pub type Happiness = HashMap<(String, String), isize>;
 
-fn parse_happiness<'a>(input: &'a str) -> Result<(People<'a>, Happiness<'a>)> {
+pub fn parse_happiness(input: &str, options: &ParseOptions) -> Result<(People, Happiness)> {
     lazy_static! {
         static ref RE: Regex = Regex::new(r"(?m)^(?P<f>[[:alpha:]]+) would (?P<n>gain|lose) (?P<a>\d+) happiness units by sitting next to (?P<t>[[:alpha:]]+)\.$").unwrap();
     }
-    let happiness: Happiness<'a> = RE
-        .captures_iter(input)
-        .map(|m| {
-            let from = m.name("f").unwrap().as_str();
-            let is_negative = &m["n"] == "lose";
-            let amount: isize = m["a"].parse().unwrap();
-            let to = m.name("t").unwrap().as_str();
-            ((from, to), if is_negative { -amount } else { amount })
-        })
-        .collect();
+    let expected = "expected 'A would gain/lose N happiness units by sitting next to B.'";
+    let happiness: Happiness = parse_lines(input, options, expected, |line| {
+        let m = RE.captures(line)?;
+        let from = m["f"].to_owned();
+        let is_negative = &m["n"] == "lose";
+        let amount: isize = m["a"].parse().ok()?;
+        let to = m["t"].to_owned();
+        Some(((from, to), if is_negative { -amount } else { amount }))
+    })?
+    .into_iter()
+    .collect();
 
     if happiness.is_empty() {
-        return Err(Error::Input("expected any input"));
+        return Err(Error::input("expected any input"));
     }
 
     let mut people = HashSet::new();
     for (f, t) in happiness.keys() {
-        people.insert(*f);
-        if !happiness.contains_key(&(t, f)) {
-            return Err(Error::Input("happiness should be specified both ways"));
+        people.insert(f.clone());
+        if !happiness.contains_key(&(t.clone(), f.clone())) {
+            return Err(Error::input("happiness should be specified both ways"));
         }
     }
 
     Ok((people, happiness))
 }
 
-fn compute_total_happiness<'a>((people, happiness): (People<'a>, Happiness<'a>)) -> isize {
-    let mut people = people.into_iter().collect::<Vec<_>>();
+/// The total change in happiness of the best seating arrangement.
+pub fn compute_total_happiness((people, happiness): &(People, Happiness)) -> isize {
+    let happiness: HashMap<(&str, &str), isize> = happiness
+        .iter()
+        .map(|((from, to), &amount)| ((from.as_str(), to.as_str()), amount))
+        .collect();
+    let mut people = people.iter().map(String::as_str).collect::<Vec<_>>();
     let heap = Heap::new(&mut people);
     heap
         .map(|permutation| {
@@ -68,18 +77,42 @@ fn compute_total_happiness<'a>((people, happiness): (People<'a>, Happiness<'a>))
         .unwrap()
 }
 
-fn part1(input: String) -> Result<isize> {
-    Ok(compute_total_happiness(parse_happiness(&input)?))
+fn part1(input: &(People, Happiness)) -> Result<isize> {
+    Ok(compute_total_happiness(input))
 }
 
-fn part2(input: String) -> Result<isize> {
-    let (mut people, mut happiness) = parse_happiness(&input)?;
+fn part2(input: &(People, Happiness)) -> Result<isize> {
+    let (mut people, mut happiness) = input.clone();
     for person in people.iter() {
-        happiness.insert(("", person), 0);
-        happiness.insert((person, ""), 0);
+        happiness.insert((String::new(), person.clone()), 0);
+        happiness.insert((person.clone(), String::new()), 0);
+    }
+    people.insert(String::new());
+    Ok(compute_total_happiness(&(people, happiness)))
+}
+
+impl InputGenerator for Solver {
+    /// The number of people.
+    const DEFAULT_SIZE: usize = 8;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut names = Vec::new();
+        for _ in 0..size.max(2) {
+            rng.name(&mut names);
+        }
+        let mut lines = Vec::new();
+        for from in &names {
+            for to in names.iter().filter(|&to| to != from) {
+                lines.push(format!(
+                    "{} would {} {} happiness units by sitting next to {}.",
+                    from,
+                    rng.pick(&["gain", "lose"]),
+                    rng.below(100),
+                    to
+                ));
+            }
+        }
+        lines.join("\n")
     }
-    people.insert("");
-    Ok(compute_total_happiness((people, happiness)))
 }
 
 #[test]
@@ -101,7 +134,7 @@ David would gain 41 happiness units by sitting next to Carol.";
         (
             ["Alice", "Bob", "Carol", "David"]
                 .iter()
-                .cloned()
+                .map(|name| name.to_string())
                 .collect::<HashSet<_>>(),
             [
                 (("Alice", "Bob"), 54),
@@ -118,13 +151,14 @@ David would gain 41 happiness units by sitting next to Carol.";
                 (("David", "Carol"), 41),
             ]
             .iter()
-            .cloned()
+            .map(|&((from, to), amount)| ((from.to_owned(), to.to_owned()), amount))
             .collect::<HashMap<_, _>>()
         ),
-        parse_happiness(EXAMPLE).unwrap()
+        parse_happiness(EXAMPLE, &ParseOptions::default()).unwrap()
     );
 
-    assert_results!(part1,
+    let parse = |input: &str| parse_happiness(input, &ParseOptions::default());
+    assert_results!(parse, part1,
         EXAMPLE => 330,
     );
 }
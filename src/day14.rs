@@ -1,44 +1,53 @@
 day!(
     day14,
-    "https://adventofcode.com/2015/day/14/input",
+    14,
+    parse_reindeers -> Reindeers,
     part1,
-    part2
+    part2,
+    params: {
+        /// How long the race lasts, in seconds.
+        time: usize = 2503,
+    }
 );
 
+use crate::framework::{parse_lines, ParseOptions};
+use crate::generate::{InputGenerator, Rng};
 use regex::Regex;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Mobility {
-    speed: usize,
-    duration: usize,
-    rest_time: usize,
+pub struct Mobility {
+    pub speed: usize,
+    pub duration: usize,
+    pub rest_time: usize,
 }
-type Reindeers<'a> = HashMap<&'a str, Mobility>;
+pub type Reindeers = HashMap<String, Mobility>;
 
-fn parse_reindeers<'a>(input: &'a str) -> Result<Reindeers<'a>> {
+pub fn parse_reindeers(input: &str, options: &ParseOptions) -> Result<Reindeers> {
     lazy_static!(
         static ref RE: Regex = Regex::new(r"(?m)^(?P<n>[[:alpha:]]+) can fly (?P<s>\d+) km/s for (?P<d>\d+) seconds, but then must rest for (?P<r>\d+) seconds\.$").unwrap();
     );
-    RE.captures_iter(input)
-        .map(|m| {
-            let name = m.name("n").unwrap().as_str();
-            let speed = m["s"].parse()?;
-            let duration = m["d"].parse()?;
-            let rest_time = m["r"].parse()?;
-            Ok((
-                name,
-                Mobility {
-                    speed,
-                    duration,
-                    rest_time,
-                },
-            ))
-        })
-        .collect()
+    let expected = "expected 'A can fly N km/s for N seconds, but then must rest for N seconds.'";
+    let reindeers = parse_lines(input, options, expected, |line| {
+        let m = RE.captures(line)?;
+        let name = m["n"].to_owned();
+        let speed = m["s"].parse().ok()?;
+        let duration = m["d"].parse().ok()?;
+        let rest_time = m["r"].parse().ok()?;
+        Some((
+            name,
+            Mobility {
+                speed,
+                duration,
+                rest_time,
+            },
+        ))
+    })?;
+    Ok(reindeers.into_iter().collect())
 }
 
-fn compute_at_time(mobility: &Mobility, time: usize) -> usize {
+/// The distance a reindeer has flown after `time` seconds.
+pub fn compute_at_time(mobility: &Mobility, time: usize) -> usize {
     let cycle_time = mobility.duration + mobility.rest_time;
     let cycle_count = time / cycle_time;
     let remaining_time = time - cycle_count * cycle_time;
@@ -50,15 +59,15 @@ fn compute_at_time(mobility: &Mobility, time: usize) -> usize {
     }
 }
 
-fn part1(input: String) -> Result<usize> {
-    Ok(parse_reindeers(&input)?
-        .into_iter()
-        .map(|(_, mobility)| compute_at_time(&mobility, 2503))
+fn part1(reindeers: &Reindeers, params: &Params) -> Result<usize> {
+    Ok(reindeers
+        .values()
+        .map(|mobility| compute_at_time(mobility, params.time))
         .max()
         .unwrap())
 }
 
-fn part2_impl(input: &str, time: usize) -> Result<usize> {
+fn part2(reindeers: &Reindeers, params: &Params) -> Result<usize> {
     #[derive(Clone)]
     struct State {
         mobility: Mobility,
@@ -66,16 +75,16 @@ fn part2_impl(input: &str, time: usize) -> Result<usize> {
         distance: usize,
     }
 
-    let mut states = parse_reindeers(input)?
-        .into_iter()
-        .map(|(_, mobility)| State {
-            mobility,
+    let mut states = reindeers
+        .values()
+        .map(|mobility| State {
+            mobility: mobility.clone(),
             points: 0,
             distance: 0,
         })
         .collect::<Vec<_>>();
 
-    for current_time in 1..time + 1 {
+    for current_time in 1..params.time + 1 {
         // Update all distances
         for state in &mut states {
             state.distance = compute_at_time(&state.mobility, current_time);
@@ -93,8 +102,21 @@ fn part2_impl(input: &str, time: usize) -> Result<usize> {
     Ok(states.into_iter().map(|x| x.points).max().unwrap())
 }
 
-fn part2(input: String) -> Result<usize> {
-    part2_impl(&input, 2503)
+impl InputGenerator for Solver {
+    /// The number of reindeer.
+    const DEFAULT_SIZE: usize = 9;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut names = Vec::new();
+        rng.lines(size, |rng| {
+            format!(
+                "{} can fly {} km/s for {} seconds, but then must rest for {} seconds.",
+                rng.name(&mut names),
+                rng.range(2..30),
+                rng.range(2..20),
+                rng.range(20..180)
+            )
+        })
+    }
 }
 
 #[test]
@@ -104,10 +126,10 @@ fn day14_test() {
 Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.";
 
     assert_eq!(
-        parse_reindeers(EXAMPLE).unwrap(),
+        parse_reindeers(EXAMPLE, &ParseOptions::default()).unwrap(),
         [
             (
-                "Comet",
+                "Comet".to_owned(),
                 Mobility {
                     speed: 14,
                     duration: 10,
@@ -115,7 +137,7 @@ Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.";
                 }
             ),
             (
-                "Dancer",
+                "Dancer".to_owned(),
                 Mobility {
                     speed: 16,
                     duration: 11,
@@ -152,9 +174,9 @@ Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.";
     );
 
     assert_eq!(
-        part2_impl(
-            EXAMPLE,
-            1000
+        part2(
+            &parse_reindeers(EXAMPLE, &ParseOptions::default()).unwrap(),
+            &Params { time: 1000 }
         )
         .unwrap(),
         689
@@ -1,46 +1,55 @@
 day!(
     day15,
-    "https://adventofcode.com/2015/day/15/input",
+    15,
+    parse_ingredients -> Vec<Ingredient>,
     part1,
-    part2
+    part2,
+    params: {
+        /// How many teaspoons of ingredients a cookie contains.
+        teaspoons: isize = 100,
+        /// How many calories the cookies of part 2 contain.
+        calories: isize = 500,
+    }
 );
 
+use crate::framework::{parse_lines, ParseOptions};
+use crate::generate::{InputGenerator, Rng};
 use regex::Regex;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Ingredient {
-    capacity: isize,
-    durability: isize,
-    flavor: isize,
-    texture: isize,
-    calories: isize,
+pub struct Ingredient {
+    pub capacity: isize,
+    pub durability: isize,
+    pub flavor: isize,
+    pub texture: isize,
+    pub calories: isize,
 }
 
-fn parse_ingredients(input: &str) -> Result<Vec<Ingredient>> {
+pub fn parse_ingredients(input: &str, options: &ParseOptions) -> Result<Vec<Ingredient>> {
     let re = Regex::new(r"(?m)^(?P<n>[[:alpha:]]+): capacity (?P<capacity>-?\d+), durability (?P<durability>-?\d+), flavor (?P<flavor>-?\d+), texture (?P<texture>-?\d+), calories (?P<calories>-?\d+)$").unwrap();
-    re.captures_iter(input)
-        .map(|m| {
-            Ok(Ingredient {
-                capacity: m["capacity"].parse()?,
-                durability: m["durability"].parse()?,
-                flavor: m["flavor"].parse()?,
-                texture: m["texture"].parse()?,
-                calories: m["calories"].parse()?,
-            })
+    let expected = "expected 'A: capacity N, durability N, flavor N, texture N, calories N'";
+    parse_lines(input, options, expected, |line| {
+        let m = re.captures(line)?;
+        Some(Ingredient {
+            capacity: m["capacity"].parse().ok()?,
+            durability: m["durability"].parse().ok()?,
+            flavor: m["flavor"].parse().ok()?,
+            texture: m["texture"].parse().ok()?,
+            calories: m["calories"].parse().ok()?,
         })
-        .collect()
+    })
 }
 
-fn for_each_combination<F>(ingredients: &Vec<Ingredient>, mut f: F) -> Result<()>
+fn for_each_combination<F>(ingredients: &[Ingredient], teaspoons: isize, mut f: F) -> Result<()>
 where
     F: FnMut(isize, isize, isize, isize) -> ()
 {
     if ingredients.len() != 4 {
-        return Err(Error::Input("can only handle 4 ingredients"));
+        return Err(Error::input("can only handle 4 ingredients"));
     }
     
-    for a in 0..101 {
-        let remainder = 100 - a;
+    for a in 0..teaspoons + 1 {
+        let remainder = teaspoons - a;
         for b in 0..remainder + 1 {
             let remainder = remainder - b;
             for c in 0..remainder + 1 {
@@ -53,10 +62,9 @@ where
     Ok(())
 }
 
-fn part1(input: String) -> Result<isize> {
-    let ingredients = parse_ingredients(&input)?;
+fn part1(ingredients: &[Ingredient], params: &Params) -> Result<isize> {
     let mut max = 0;
-    for_each_combination(&ingredients, |a, b, c, d| #[rustfmt::skip] {
+    for_each_combination(ingredients, params.teaspoons, |a, b, c, d| #[rustfmt::skip] {
         let capacity   = isize::max(0, a * ingredients[0].capacity   + b * ingredients[1].capacity   + c * ingredients[2].capacity   + d * ingredients[3].capacity  );
         let durability = isize::max(0, a * ingredients[0].durability + b * ingredients[1].durability + c * ingredients[2].durability + d * ingredients[3].durability);
         let flavor     = isize::max(0, a * ingredients[0].flavor     + b * ingredients[1].flavor     + c * ingredients[2].flavor     + d * ingredients[3].flavor    );
@@ -68,21 +76,54 @@ fn part1(input: String) -> Result<isize> {
     Ok(max)
 }
 
-fn part2(input: String) -> Result<isize> {
-    let ingredients = parse_ingredients(&input)?;
+fn part2(ingredients: &[Ingredient], params: &Params) -> Result<isize> {
     let mut max = 0;
-    for_each_combination(&ingredients, |a, b, c, d| #[rustfmt::skip] {
+    for_each_combination(ingredients, params.teaspoons, |a, b, c, d| #[rustfmt::skip] {
         let capacity   = isize::max(0, a * ingredients[0].capacity   + b * ingredients[1].capacity   + c * ingredients[2].capacity   + d * ingredients[3].capacity  );
         let durability = isize::max(0, a * ingredients[0].durability + b * ingredients[1].durability + c * ingredients[2].durability + d * ingredients[3].durability);
         let flavor     = isize::max(0, a * ingredients[0].flavor     + b * ingredients[1].flavor     + c * ingredients[2].flavor     + d * ingredients[3].flavor    );
         let texture    = isize::max(0, a * ingredients[0].texture    + b * ingredients[1].texture    + c * ingredients[2].texture    + d * ingredients[3].texture   );
         let calories   = isize::max(0, a * ingredients[0].calories   + b * ingredients[1].calories   + c * ingredients[2].calories   + d * ingredients[3].calories  );
         let value = capacity * durability * flavor * texture;
-        if calories == 500 && value > max { max = value; }
+        if calories == params.calories && value > max { max = value; }
     })?;
     Ok(max)
 }
 
+impl InputGenerator for Solver {
+    /// The number of ingredients, the solution only handles 4.
+    const DEFAULT_SIZE: usize = 4;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // Like the real ingredients, each one makes up for the others in one
+        // property, so that the best cookie doesn't score 0
+        let mut properties = (0..size)
+            .map(|_| {
+                (0..4)
+                    .map(|_| if rng.chance(1, 2) { 0 } else { -(rng.range(1..4) as isize) })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for i in 0..size {
+            let others = (0..size).filter(|&k| k % 4 != i % 4).map(|k| properties[k][i % 4]).sum::<isize>();
+            properties[i][i % 4] = rng.range(1..4) as isize - others;
+        }
+        let mut names = Vec::new();
+        let mut lines = Vec::new();
+        for properties in &properties {
+            lines.push(format!(
+                "{}: capacity {}, durability {}, flavor {}, texture {}, calories {}",
+                rng.name(&mut names),
+                properties[0],
+                properties[1],
+                properties[2],
+                properties[3],
+                rng.range(1..10)
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
 #[test]
 fn day15_test() {
     const EXAMPLE: &'static str =
@@ -106,6 +147,6 @@ Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3";
                 calories: 3,
             }
         ],
-        parse_ingredients(EXAMPLE).unwrap()
+        parse_ingredients(EXAMPLE, &ParseOptions::default()).unwrap()
     );
 }
@@ -1,17 +1,19 @@
 day!(
     day16,
-    "https://adventofcode.com/2015/day/16/input",
+    16,
+    parse_sues -> Vec<Sue>,
     part1,
     part2
 );
 
+use crate::generate::{InputGenerator, Rng};
 use regex::Regex;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Sue {
-    index: usize,
-    properties: HashMap<String, usize>,
+pub struct Sue {
+    pub index: usize,
+    pub properties: HashMap<String, usize>,
 }
 
 fn parse_line(line: &str) -> Result<Sue> {
@@ -20,7 +22,7 @@ fn parse_line(line: &str) -> Result<Sue> {
         static ref PROP: Regex = Regex::new(r"(?P<key>[[:alpha:]]+): (?P<value>\d+)(, )?").unwrap();
     }
 
-    let captures = MAIN.captures(line).ok_or(Error::Input("invalid format"))?;
+    let captures = MAIN.captures(line).ok_or(Error::input("invalid format"))?;
     let index = captures["index"].parse().unwrap();
     let props = captures.name("props").unwrap().as_str();
     let properties = PROP
@@ -36,6 +38,10 @@ fn parse_line(line: &str) -> Result<Sue> {
     Ok(Sue { index, properties })
 }
 
+pub fn parse_sues(input: &str) -> Result<Vec<Sue>> {
+    input.lines().map(parse_line).collect()
+}
+
 const KNOWN_PROPS: [(&'static str, usize); 10] = [
     ("children", 3),
     ("cats", 7),
@@ -49,9 +55,8 @@ const KNOWN_PROPS: [(&'static str, usize); 10] = [
     ("perfumes", 1),
 ];
 
-fn part1(input: String) -> Result<usize> {
-    let mut sues: Vec<Sue> = input.lines().map(parse_line).collect::<Result<_>>()?;
-    sues.retain(|sue| {
+fn part1(sues: &[Sue]) -> Result<usize> {
+    let sues: Vec<&Sue> = sues.iter().filter(|sue| {
         for &(key, value) in KNOWN_PROPS.iter() {
             if let Some(&sue_value) = sue.properties.get(key) {
                 if sue_value != value {
@@ -60,18 +65,17 @@ fn part1(input: String) -> Result<usize> {
             }
         }
         true
-    });
+    }).collect();
 
     if sues.len() != 1 {
-        return Err(Error::Input("no sues matching the properties found"));
+        return Err(Error::input("no sues matching the properties found"));
     }
 
     Ok(sues[0].index)
 }
 
-fn part2(input: String) -> Result<usize> {
-    let mut sues: Vec<Sue> = input.lines().map(parse_line).collect::<Result<_>>()?;
-    sues.retain(|sue| {
+fn part2(sues: &[Sue]) -> Result<usize> {
+    let sues: Vec<&Sue> = sues.iter().filter(|sue| {
         for &(key, value) in KNOWN_PROPS.iter() {
             if let Some(&sue_value) = sue.properties.get(key) {
                 if !match key {
@@ -84,15 +88,58 @@ fn part2(input: String) -> Result<usize> {
             }
         }
         true
-    });
+    }).collect();
 
     if sues.len() != 1 {
-        return Err(Error::Input("no sues matching the properties found"));
+        return Err(Error::input("no sues matching the properties found"));
     }
 
     Ok(sues[0].index)
 }
 
+impl InputGenerator for Solver {
+    /// The number of Sues.
+    const DEFAULT_SIZE: usize = 500;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // One Sue matches the properties exactly and another one matches the
+        // ranges of part 2, every other Sue contradicts both.
+        let (ranged, exact): (Vec<_>, Vec<_>) = KNOWN_PROPS
+            .iter()
+            .cloned()
+            .partition(|(key, _)| ["cats", "trees", "pomeranians", "goldfish"].contains(key));
+        let size = size.max(2);
+        let part1_sue = rng.below(size);
+        let part2_sue = (part1_sue + rng.range(1..size)) % size;
+        let mut lines = Vec::new();
+        for sue in 0..size {
+            let mut props = Vec::new();
+            let mut others = KNOWN_PROPS.to_vec();
+            if sue == part1_sue {
+                // A ranged property with its exact value fails part 2
+                props.push(*rng.pick(&ranged));
+            } else if sue == part2_sue {
+                let key = *rng.pick(&["cats", "trees"]);
+                let value = KNOWN_PROPS.iter().find(|prop| prop.0 == key).unwrap().1;
+                props.push((key, value + rng.range(1..5)));
+                others = exact.clone();
+            } else {
+                let (key, value) = *rng.pick(&exact);
+                props.push((key, (value + rng.range(1..10)) % 10));
+            }
+            others.retain(|(key, _)| props[0].0 != *key);
+            rng.shuffle(&mut others);
+            for &(key, value) in &others[..2] {
+                let value = if sue == part1_sue || sue == part2_sue { value } else { rng.below(10) };
+                props.push((key, value));
+            }
+            rng.shuffle(&mut props);
+            let props = props.iter().map(|(key, value)| format!("{}: {}", key, value)).collect::<Vec<_>>();
+            lines.push(format!("Sue {}: {}", sue + 1, props.join(", ")));
+        }
+        lines.join("\n")
+    }
+}
+
 #[test]
 fn day16_test() {
     assert_eq!(
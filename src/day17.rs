@@ -1,19 +1,31 @@
 day!(
     day17,
-    "https://adventofcode.com/2015/day/17/input",
+    17,
+    parse_sizes -> Vec<usize>,
     part1,
-    part2
+    part2,
+    params: {
+        /// How many liters of eggnog the containers must hold.
+        liters: usize = 150,
+    }
 );
 
+use crate::generate::{InputGenerator, Rng};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
-fn combinations(input: &str, total: usize) -> Result<usize> {
+/// Parses the container sizes, largest first.
+pub fn parse_sizes(input: &str) -> Result<Vec<usize>> {
     let mut sizes: Vec<usize> = input.lines().map(|x| Ok(x.parse()?)).collect::<Result<_>>()?;
     sizes.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(sizes)
+}
+
+/// The number of combinations of containers that hold exactly `total` liters.
+pub fn combinations(sizes: &[usize], total: usize) -> Result<usize> {
     let mut combinations = 0;
 
-    fn visit(total: usize, idx: usize, sizes: &Vec<usize>, combinations: &mut usize, previous: usize) {
+    fn visit(total: usize, idx: usize, sizes: &[usize], combinations: &mut usize, previous: usize) {
         let current = sizes[idx];
         match (previous + current).cmp(&total) {
             Ordering::Less => {
@@ -41,22 +53,22 @@ fn combinations(input: &str, total: usize) -> Result<usize> {
     };
 
     for i in 0..upper {
-        visit(total, i, &mut sizes, &mut combinations, 0);
+        visit(total, i, sizes, &mut combinations, 0);
     }
 
     Ok(combinations)
 }
 
-fn part1(input: String) -> Result<usize> {
-    combinations(&input, 150)
+fn part1(sizes: &[usize], params: &Params) -> Result<usize> {
+    combinations(sizes, params.liters)
 }
 
-fn minimum_combinations(input: &str, total: usize) -> Result<usize> {
-    let mut sizes: Vec<usize> = input.lines().map(|x| Ok(x.parse()?)).collect::<Result<_>>()?;
-    sizes.sort_unstable_by(|a, b| b.cmp(a));
+/// The number of combinations of containers that hold exactly `total` liters
+/// using as few containers as possible.
+pub fn minimum_combinations(sizes: &[usize], total: usize) -> Result<usize> {
     let mut combinations = HashMap::new();
 
-    fn visit(total: usize, idx: usize, sizes: &Vec<usize>, combinations: &mut HashMap<usize, usize>, previous: usize, count: usize) {
+    fn visit(total: usize, idx: usize, sizes: &[usize], combinations: &mut HashMap<usize, usize>, previous: usize, count: usize) {
         let current = sizes[idx];
         match (previous + current).cmp(&total) {
             Ordering::Less => {
@@ -84,18 +96,41 @@ fn minimum_combinations(input: &str, total: usize) -> Result<usize> {
     };
 
     for i in 0..upper {
-        visit(total, i, &mut sizes, &mut combinations, 0, 0);
+        visit(total, i, sizes, &mut combinations, 0, 0);
     }
 
     Ok(combinations.into_iter().min_by_key(|v| v.0).unwrap().1)
 }
 
-fn part2(input: String) -> Result<usize> {
-    minimum_combinations(&input, 150)
+fn part2(sizes: &[usize], params: &Params) -> Result<usize> {
+    minimum_combinations(sizes, params.liters)
+}
+
+impl InputGenerator for Solver {
+    /// The number of containers.
+    const DEFAULT_SIZE: usize = 20;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // Some of the containers add up to exactly the default amount of
+        // eggnog, the others are random
+        let mut remaining = Params::default().liters;
+        let mut sizes = Vec::new();
+        while remaining > 50 {
+            let size = rng.range(5..remaining.min(55) - 4);
+            sizes.push(size);
+            remaining -= size;
+        }
+        sizes.push(remaining);
+        while sizes.len() < size {
+            sizes.push(rng.range(5..51));
+        }
+        rng.shuffle(&mut sizes);
+        sizes.iter().map(|size| size.to_string()).collect::<Vec<_>>().join("\n")
+    }
 }
 
 #[test]
 fn day17_test() {
-    assert_eq!(combinations("20\n15\n10\n5\n5", 25).unwrap(), 4);
-    assert_eq!(minimum_combinations("20\n15\n10\n5\n5", 25).unwrap(), 3);
+    let sizes = parse_sizes("20\n15\n10\n5\n5").unwrap();
+    assert_eq!(combinations(&sizes, 25).unwrap(), 4);
+    assert_eq!(minimum_combinations(&sizes, 25).unwrap(), 3);
 }
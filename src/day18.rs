@@ -1,28 +1,34 @@
 day!(
     day18,
-    "https://adventofcode.com/2015/day/18/input",
+    18,
+    parse_grid -> LightGrid,
     part1,
-    part2
+    part2,
+    params: {
+        /// How many steps of the animation to run.
+        steps: usize = 100,
+    }
 );
 
+use crate::generate::{InputGenerator, Rng};
 use std::fmt::{self, Display, Formatter};
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct LightGrid {
+pub struct LightGrid {
     size: (usize, usize),
     data: Vec<bool>,
 }
 
-struct LightGridNeighbors {
+pub struct LightGridNeighbors {
     size: (usize, usize),
     idx: usize,
     base: (usize, usize),
 }
 
 impl LightGrid {
-    fn neighbors(&self, position: (usize, usize)) -> LightGridNeighbors {
+    pub fn neighbors(&self, position: (usize, usize)) -> LightGridNeighbors {
         LightGridNeighbors {
             size: self.size,
             idx: 0,
@@ -30,7 +36,8 @@ impl LightGrid {
         }
     }
 
-    fn transform_1(&self) -> LightGrid {
+    /// One step of the animation.
+    pub fn transform_1(&self) -> LightGrid {
         let data = self
             .data
             .iter()
@@ -54,7 +61,8 @@ impl LightGrid {
         }
     }
 
-    fn transform_2(&self) -> LightGrid {
+    /// One step of the animation, with the corner lights stuck on.
+    pub fn transform_2(&self) -> LightGrid {
         let mut new = self.transform_1();
         let (w, h) = self.size;
         new[(0, 0)] = true;
@@ -64,7 +72,7 @@ impl LightGrid {
         new
     }
 
-    fn count_on(&self) -> usize {
+    pub fn count_on(&self) -> usize {
         self.data.iter().filter(|s| **s).count()
     }
 }
@@ -137,12 +145,15 @@ impl FromStr for LightGrid {
     fn from_str(s: &str) -> Result<LightGrid> {
         let lines: Vec<_> = s.lines().collect();
         if lines.is_empty() {
-            return Err(Error::Input("empty string"));
+            return Err(Error::input("empty string"));
         }
         let h = lines.len();
         let w = lines[0].len();
+        if w == 0 {
+            return Err(Error::input("empty line"));
+        }
         if !lines.iter().skip(1).all(|x| x.len() == w) {
-            return Err(Error::Input("inconsistent width"));
+            return Err(Error::input("inconsistent width"));
         }
         let mut data = Vec::with_capacity(w * h);
         for line in lines {
@@ -150,7 +161,7 @@ impl FromStr for LightGrid {
                 match c {
                     '#' => data.push(true),
                     '.' => data.push(false),
-                    _ => return Err(Error::Input("invalid character, expected # or .")),
+                    _ => return Err(Error::input("invalid character, expected # or .")),
                 }
             }
         }
@@ -158,25 +169,38 @@ impl FromStr for LightGrid {
     }
 }
 
-fn part1(input: String) -> Result<usize> {
-    let mut grid: LightGrid = input.parse()?;
-    for _ in 0..100 {
+pub fn parse_grid(input: &str) -> Result<LightGrid> {
+    input.parse()
+}
+
+fn part1(grid: &LightGrid, params: &Params) -> Result<usize> {
+    let mut grid = grid.clone();
+    for _ in 0..params.steps {
         grid = grid.transform_1();
     }
     Ok(grid.count_on())
 }
-fn part2(input: String) -> Result<usize> {
-    let mut grid: LightGrid = input.parse()?;
+fn part2(grid: &LightGrid, params: &Params) -> Result<usize> {
+    let mut grid = grid.clone();
+    let (w, h) = grid.size;
     grid[(0, 0)] = true;
-    grid[(99, 0)] = true;
-    grid[(0, 99)] = true;
-    grid[(99, 99)] = true;
-    for _ in 0..100 {
+    grid[(w - 1, 0)] = true;
+    grid[(0, h - 1)] = true;
+    grid[(w - 1, h - 1)] = true;
+    for _ in 0..params.steps {
         grid = grid.transform_2();
     }
     Ok(grid.count_on())
 }
 
+impl InputGenerator for Solver {
+    /// The width and height of the grid.
+    const DEFAULT_SIZE: usize = 100;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        rng.lines(size.max(1), |rng| (0..size.max(1)).map(|_| *rng.pick(&['#', '.'])).collect())
+    }
+}
+
 #[test]
 fn day18_test() {
     const INITIAL: &'static str = ".#.#.#
@@ -304,4 +328,6 @@ fn day18_test() {
 #.#...
 ##...#"
     );
+
+    assert!("\n".parse::<LightGrid>().is_err());
 }
@@ -1,41 +1,41 @@
 day!(
     day19,
-    "https://adventofcode.com/2015/day/19/input",
+    19,
+    parse_input -> (Vec<(String, String)>, String),
     part1,
     part2
 );
 
+use crate::generate::{InputGenerator, Rng};
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
 use std::mem::drop;
 
-fn parse_input<'s>(input: &'s str) -> Result<(Vec<(&'s str, &'s str)>, &'s str)> {
+pub fn parse_input(input: &str) -> Result<(Vec<(String, String)>, String)> {
     let mut iter = input.lines();
     let mut rules = Vec::new();
     loop {
-        let line = iter.next().ok_or(Error::Input("unexpected end of input"))?;
+        let line = iter.next().ok_or(Error::input("unexpected end of input"))?;
         if line.len() == 0 {
             break;
         }
         let mut parts = line.split(" => ");
-        let from = parts.next().ok_or(Error::Input("expected input atom"))?;
-        let into = parts.next().ok_or(Error::Input("expected output atom"))?;
+        let from = parts.next().ok_or(Error::input("expected input atom"))?;
+        let into = parts.next().ok_or(Error::input("expected output atom"))?;
         if let Some(_) = parts.next() {
-            return Err(Error::Input("expected end of line"));
+            return Err(Error::input("expected end of line"));
         }
-        rules.push((from, into));
+        rules.push((from.to_owned(), into.to_owned()));
     }
-    let molecule = iter.next().ok_or(Error::Input("unexpected end of input"))?;
+    let molecule = iter.next().ok_or(Error::input("unexpected end of input"))?;
     if let Some(_) = iter.next() {
-        return Err(Error::Input("expected end of input"));
+        return Err(Error::input("expected end of input"));
     }
-    Ok((rules, molecule))
+    Ok((rules, molecule.to_owned()))
 }
 
-fn part1(input: String) -> Result<usize> {
-    let (rules, molecule) = parse_input(&input)?;
-
+fn part1((rules, molecule): &(Vec<(String, String)>, String)) -> Result<usize> {
     let mut combinations = HashSet::new();
 
     for (from, to) in rules {
@@ -44,7 +44,7 @@ fn part1(input: String) -> Result<usize> {
                 let mut substitution =
                     String::with_capacity(molecule.len() - from.len() + to.len());
                 substitution.push_str(&molecule[0..i]);
-                substitution.push_str(&to);
+                substitution.push_str(to);
                 substitution.push_str(&molecule[i + from.len()..]);
                 combinations.insert(substitution);
             }
@@ -65,18 +65,18 @@ fn string_to_molecule<'s>(s: &'s str, atom_map: &mut HashMap<&'s str, Atom>) ->
     let mut bytes = s.bytes().peekable();
     while let Some(byte) = bytes.next() {
         if !byte.is_ascii_alphabetic() {
-            return Err(Error::Input("non-alphabetic character in molecules"));
+            return Err(Error::input("non-alphabetic character in molecules"));
         }
         let atom_length = if byte == b'e' {
             if s != "e" {
-                return Err(Error::Input(
+                return Err(Error::input(
                     "electron isn't allowed within a complex molecule",
                 ));
             }
             1
         } else {
             if !byte.is_ascii_uppercase() {
-                return Err(Error::Input(
+                return Err(Error::input(
                     "expected atom to start with an uppercase character",
                 ));
             }
@@ -95,7 +95,7 @@ fn string_to_molecule<'s>(s: &'s str, atom_map: &mut HashMap<&'s str, Atom>) ->
         idx += atom_length;
         let atom_map_len = atom_map.len();
         if atom_map_len == 256 {
-            return Err(Error::Input("too many unique atoms"));
+            return Err(Error::input("too many unique atoms"));
         }
         let atom = *atom_map.entry(slice).or_insert(atom_map_len as Atom);
         molecule.push(atom);
@@ -128,26 +128,24 @@ fn find_substr(target: &[u8], substr: &[u8]) -> Option<usize> {
 // it could've been produced (out of the 43 maximum). A naive BFS
 // would lead towards 11.5^n expansions, A* on the other hand can
 // behave closer to a DFS when it's on the right track.
-fn part2(input: String) -> Result<usize> {
-    let (rules, molecule) = parse_input(&input)?;
-
+fn part2((rules, molecule): &(Vec<(String, String)>, String)) -> Result<usize> {
     let mut atom_map = HashMap::new();
     atom_map.insert("e", 0);
     let mut rules = rules
-        .into_iter()
+        .iter()
         .map(|(from, into)| {
-            let from = string_to_molecule(&from, &mut atom_map)?;
+            let from = string_to_molecule(from, &mut atom_map)?;
             if from.len() != 1 {
-                return Err(Error::Input(
+                return Err(Error::input(
                     "mapping can only be from one atom to a molecule",
                 ));
             }
-            let into = string_to_molecule(&into, &mut atom_map)?;
+            let into = string_to_molecule(into, &mut atom_map)?;
             Ok((into, from[0]))
         })
         .collect::<Result<Vec<_>>>()?;
     rules.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
-    let molecule = string_to_molecule(&molecule, &mut atom_map)?;
+    let molecule = string_to_molecule(molecule, &mut atom_map)?;
 
     let mut astar = crate::astar::AStar::new();
     astar
@@ -172,12 +170,58 @@ fn part2(input: String) -> Result<usize> {
             |molecule| molecule.len() == 1 && molecule[0] == 0,
         )
         .map(|path| path.last().unwrap().1)
-        .ok_or(Error::Input("no solution found for input"))
+        .ok_or(Error::input("no solution found for input"))
+}
+
+impl InputGenerator for Solver {
+    /// The number of replacements that make the medicine molecule. The real
+    /// molecules take about 200, but random grammars are more ambiguous, and
+    /// part 2 gets slow on them long before that.
+    const DEFAULT_SIZE: usize = 100;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // Like the real grammars, every atom but Rn, Y and Ar can be replaced,
+        // either by two atoms or by a molecule such as XRnYAr. Most rules are
+        // of the second kind, which keeps the search of part 2 manageable. The
+        // molecule is made from e with the rules, so it can always be made.
+        const ATOMS: [&str; 10] = ["Al", "B", "Ca", "F", "H", "Mg", "N", "O", "P", "Ti"];
+        let atom = |rng: &mut Rng| *rng.pick(&ATOMS);
+        let mut rules: Vec<(&str, Vec<&str>)> = Vec::new();
+        for _ in 0..3 {
+            rules.push(("e", vec![atom(rng), atom(rng)]));
+        }
+        for &from in &ATOMS {
+            for _ in 0..rng.range(1..3) {
+                let into = match rng.below(3) {
+                    0 => vec![atom(rng), "Rn", atom(rng), "Ar"],
+                    1 => vec![atom(rng), "Rn", atom(rng), "Y", atom(rng), "Ar"],
+                    _ => vec![atom(rng), atom(rng)],
+                };
+                rules.push((from, into));
+            }
+        }
+
+        let mut molecule = rules[rng.below(3)].1.clone();
+        for _ in 1..size {
+            let replaceable = (0..molecule.len()).filter(|&i| ATOMS.contains(&molecule[i])).collect::<Vec<_>>();
+            let idx = *rng.pick(&replaceable);
+            let options = rules.iter().filter(|(from, _)| *from == molecule[idx]).collect::<Vec<_>>();
+            let into = rng.pick(&options).1.clone();
+            molecule.splice(idx..idx + 1, into);
+        }
+
+        let mut lines = rules
+            .iter()
+            .map(|(from, into)| format!("{} => {}", from, into.concat()))
+            .collect::<Vec<_>>();
+        lines.push(String::new());
+        lines.push(molecule.concat());
+        lines.join("\n")
+    }
 }
 
 #[test]
 fn day19_test() {
-    assert_results!(part1, "\
+    assert_results!(parse_input, part1, "\
 H => HO
 H => OH
 O => HH
@@ -190,7 +234,7 @@ O => HH
 HOHOHO" => 7,
     );
 
-    assert_results!(part2, "\
+    assert_results!(parse_input, part2, "\
 e => H
 e => O
 H => HO
@@ -1,10 +1,13 @@
 day!(
     day20,
-    "https://adventofcode.com/2015/day/20/input",
+    20,
+    parse_presents -> u64,
     part1,
-    part2
+    part2,
+    timeout: 120
 );
 
+use crate::generate::{InputGenerator, Rng};
 use itertools::Itertools;
 use std::iter;
 
@@ -54,10 +57,15 @@ where
     sum
 }
 
+pub fn parse_presents(input: &str) -> Result<u64> {
+    Ok(input.parse()?)
+}
 
-fn part1(input: String) -> Result<usize> {
-    let input: u64 = input.parse()?;
+fn part1(&input: &u64) -> Result<usize> {
     for house_number in 1.. {
+        if house_number % 4096 == 0 {
+            crate::framework::check_cancelled()?;
+        }
         // The amount of presents is the sum of all the positive integer divisors.
         // Multiplied by 10.
         if fold_divisors(house_number, |acc, div| acc + div) * 10 >= input {
@@ -67,10 +75,11 @@ fn part1(input: String) -> Result<usize> {
     unreachable!()
 }
 
-fn part2(input: String) -> Result<u64> {
-    let input: u64 = input.parse()?;
-
+fn part2(&input: &u64) -> Result<u64> {
     for house_number in 1u64.. {
+        if house_number % 4096 == 0 {
+            crate::framework::check_cancelled()?;
+        }
         // The amount of presents is the sum of all the positive integer divisors
         // where those divisors (the elves) haven't previously visited 50 house
         // numbers. Multiplied by 11.
@@ -90,6 +99,18 @@ fn part2(input: String) -> Result<u64> {
     unreachable!()
 }
 
+impl InputGenerator for Solver {
+    /// The number of digits of the number of presents.
+    const DEFAULT_SIZE: usize = 8;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut presents = rng.range(1..10).to_string();
+        for _ in 1..size {
+            presents.push((b'0' + rng.below(10) as u8) as char);
+        }
+        presents
+    }
+}
+
 #[test]
 fn day20_test() {
     use std::ops::Add;
@@ -1,17 +1,23 @@
 day!(
     day21,
-    "https://adventofcode.com/2015/day/21/input",
+    21,
+    parse_input -> Unit,
     part1,
-    part2
+    part2,
+    params: {
+        /// The hit points the player starts with.
+        player_hp: usize = 100,
+    }
 );
 
+use crate::generate::{InputGenerator, Rng};
 use std::iter::{DoubleEndedIterator, ExactSizeIterator, Iterator};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Unit {
-    hp: usize,
-    damage: usize,
-    armor: usize,
+pub struct Unit {
+    pub hp: usize,
+    pub damage: usize,
+    pub armor: usize,
 }
 
 // Combinatorics:
@@ -102,9 +108,9 @@ fn calculate_effective_damage(from: &Unit, to: &Unit) -> usize {
     }
 }
 
-fn does_player_win_fight(boss: &Unit, loadout: &Loadout) -> bool {
+fn does_player_win_fight(boss: &Unit, loadout: &Loadout, player_hp: usize) -> bool {
     let player = Unit {
-        hp: 100,
+        hp: player_hp,
         damage: loadout.damage,
         armor: loadout.armor,
     };
@@ -118,32 +124,30 @@ fn does_player_win_fight(boss: &Unit, loadout: &Loadout) -> bool {
     boss_damage_before_death < player.hp
 }
 
-fn part1(input: String) -> Result<usize> {
-    let boss = parse_input(&input)?;
+fn part1(boss: &Unit, params: &Params) -> Result<usize> {
     let mut loadouts = loadouts().collect::<Vec<_>>();
     loadouts.sort_unstable_by(|a, b| a.cost.cmp(&b.cost));
 
     for loadout in loadouts {
-        if does_player_win_fight(&boss, &loadout) {
+        if does_player_win_fight(boss, &loadout, params.player_hp) {
             return Ok(loadout.cost);
         }
     }
 
-    return Err(Error::Input(
+    Err(Error::input(
         "no loadout exists that would let the player defeat the boss",
-    ));
+    ))
 }
 
-fn part2(input: String) -> Result<usize> {
-    let boss = parse_input(&input)?;
+fn part2(boss: &Unit, params: &Params) -> Result<usize> {
     loadouts()
-        .filter(|loadout| !does_player_win_fight(&boss, loadout))
+        .filter(|loadout| !does_player_win_fight(boss, loadout, params.player_hp))
         .map(|loadout| loadout.cost)
         .max()
-        .ok_or(Error::Input("no loadout exists where the boss wins"))
+        .ok_or(Error::input("no loadout exists where the boss wins"))
 }
 
-fn parse_input(input: &str) -> Result<Unit> {
+pub fn parse_input(input: &str) -> Result<Unit> {
     use regex::Regex;
     lazy_static! {
         static ref RE: Regex = Regex::new(
@@ -151,10 +155,18 @@ fn parse_input(input: &str) -> Result<Unit> {
         )
         .unwrap();
     };
-    let captures = RE.captures(input).ok_or(Error::Input("invalid input"))?;
+    let captures = RE.captures(input).ok_or(Error::input("invalid input"))?;
     let hp = captures["hp"].parse()?;
     let damage = captures["damage"].parse()?;
     let armor = captures["armor"].parse()?;
 
     Ok(Unit { hp, damage, armor })
 }
+
+impl InputGenerator for Solver {
+    /// The hit points of the boss.
+    const DEFAULT_SIZE: usize = 100;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        format!("Hit Points: {}\nDamage: {}\nArmor: {}", size, rng.range(4..11), rng.range(0..4))
+    }
+}
@@ -1,10 +1,18 @@
 day!(
     day22,
-    "https://adventofcode.com/2015/day/22/input",
+    22,
+    parse_input -> Boss,
     part1,
-    part2
+    part2,
+    params: {
+        /// The hit points the player starts with.
+        player_hp: i16 = 50,
+        /// The mana the player starts with.
+        player_mana: i16 = 500,
+    }
 );
 
+use crate::generate::{InputGenerator, Rng};
 use arrayvec::ArrayVec;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -14,9 +22,9 @@ struct Player {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Boss {
-    hp: i16,
-    damage: i16,
+pub struct Boss {
+    pub hp: i16,
+    pub damage: i16,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -148,10 +156,9 @@ impl State {
     }
 }
 
-fn part1(input: String) -> Result<usize> {
-    let boss = parse_input(&input)?;
+fn part1(&boss: &Boss, params: &Params) -> Result<usize> {
     let state = State {
-        player: Player { hp: 50, mana: 500 },
+        player: Player { hp: params.player_hp, mana: params.player_mana },
         boss,
         effects: Effects {
             shield: 0,
@@ -164,14 +171,13 @@ fn part1(input: String) -> Result<usize> {
     let mut astar = crate::astar::AStar::new();
     let path = astar
         .solve(state, State::next_states, |_| 0, |state| state.boss.hp <= 0)
-        .ok_or(Error::Input("no solution found"))?;
+        .ok_or(Error::input("no solution found"))?;
     Ok(path.last().unwrap().1)
 }
 
-fn part2(input: String) -> Result<usize> {
-    let boss = parse_input(&input)?;
+fn part2(&boss: &Boss, params: &Params) -> Result<usize> {
     let state = State {
-        player: Player { hp: 50, mana: 500 },
+        player: Player { hp: params.player_hp, mana: params.player_mana },
         boss,
         effects: Effects {
             shield: 0,
@@ -192,20 +198,29 @@ fn part2(input: String) -> Result<usize> {
                 State::next_states(&state)
             }
         }, |_| 0, |state| state.boss.hp <= 0)
-        .ok_or(Error::Input("no solution found"))?;
+        .ok_or(Error::input("no solution found"))?;
     Ok(path.last().unwrap().1)
 
 }
 
-fn parse_input(input: &str) -> Result<Boss> {
+pub fn parse_input(input: &str) -> Result<Boss> {
     use regex::Regex;
     lazy_static! {
         static ref RE: Regex =
             Regex::new(r"^Hit Points: (?P<hp>\d+)\nDamage: (?P<damage>\d+)$").unwrap();
     };
-    let captures = RE.captures(input).ok_or(Error::Input("invalid input"))?;
+    let captures = RE.captures(input).ok_or(Error::input("invalid input"))?;
     let hp = captures["hp"].parse()?;
     let damage = captures["damage"].parse()?;
 
     Ok(Boss { hp, damage })
 }
+
+impl InputGenerator for Solver {
+    /// The hit points of the boss, at most `i16::MAX`.
+    const DEFAULT_SIZE: usize = 55;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let hp = size.min(i16::MAX as usize);
+        format!("Hit Points: {}\nDamage: {}", hp, rng.range(5..11))
+    }
+}
@@ -1,10 +1,13 @@
 day!(
     day23,
-    "https://adventofcode.com/2015/day/23/input",
+    23,
+    parse_input -> Vec<Instruction>,
     part1,
     part2
 );
 
+use crate::generate::{InputGenerator, Rng};
+
 fn access(regs: &mut [usize; 2], reg: Register) -> &mut usize {
     match reg {
         Register::A => &mut regs[0],
@@ -12,8 +15,9 @@ fn access(regs: &mut [usize; 2], reg: Register) -> &mut usize {
     }
 }
 
-fn solve(input: String, start_a: usize) -> Result<usize> {
-    let instructions = parse_input(&input)?;
+/// Runs the program with register a starting at `start_a`, and returns the
+/// value of register b once it halts.
+pub fn solve(instructions: &[Instruction], start_a: usize) -> Result<usize> {
     let mut ip = 0isize;
     let regs = &mut [start_a, 0];
 
@@ -53,21 +57,21 @@ fn solve(input: String, start_a: usize) -> Result<usize> {
     Ok(regs[1])
 }
 
-fn part1(input: String) -> Result<usize> {
-    solve(input, 0)
+fn part1(instructions: &[Instruction]) -> Result<usize> {
+    solve(instructions, 0)
 }
-fn part2(input: String) -> Result<usize> {
-    solve(input, 1)
+fn part2(instructions: &[Instruction]) -> Result<usize> {
+    solve(instructions, 1)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Register {
+pub enum Register {
     A,
     B,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Instruction {
+pub enum Instruction {
     Half(Register),
     Tripple(Register),
     Increment(Register),
@@ -76,7 +80,7 @@ enum Instruction {
     JumpIfOne(Register, isize),
 }
 
-fn parse_input(input: &str) -> Result<Vec<Instruction>> {
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>> {
     fn parse_register(s: &str) -> Result<Register> {
         if s.len() == 1 {
             match s.as_bytes()[0] {
@@ -85,30 +89,74 @@ fn parse_input(input: &str) -> Result<Vec<Instruction>> {
                 _ => {}
             }
         }
-        return Err(Error::Input("invalid register"));
+        Err(Error::input("invalid register"))
     }
     input
         .split('\n')
-        .map(|line| {
+        .enumerate()
+        .map(|(idx, line)| {
+            let register = |s: &str| parse_register(s).map_err(|e| e.at(idx, line, s));
+            let offset = |s: &str| {
+                s.parse()
+                    .map_err(|_| Error::input("invalid offset").at(idx, line, s))
+            };
             if line.len() < 5 {
-                return Err(Error::Input("invalid instruction length"));
+                return Err(Error::input("invalid instruction length").at(idx, line, line));
             }
-            let constructor = match &line[0..4] {
-                "hlf " => return Ok(Instruction::Half(parse_register(&line[4..])?)),
-                "tpl " => return Ok(Instruction::Tripple(parse_register(&line[4..])?)),
-                "inc " => return Ok(Instruction::Increment(parse_register(&line[4..])?)),
-                "jmp " => return Ok(Instruction::Jump(line[4..].parse()?)),
-                "jie " => Instruction::JumpIfEven,
-                "jio " => Instruction::JumpIfOne,
-                _ => return Err(Error::Input("invalid instruction")),
+            let constructor = match line.get(0..4) {
+                Some("hlf ") => return Ok(Instruction::Half(register(&line[4..])?)),
+                Some("tpl ") => return Ok(Instruction::Tripple(register(&line[4..])?)),
+                Some("inc ") => return Ok(Instruction::Increment(register(&line[4..])?)),
+                Some("jmp ") => return Ok(Instruction::Jump(offset(&line[4..])?)),
+                Some("jie ") => Instruction::JumpIfEven,
+                Some("jio ") => Instruction::JumpIfOne,
+                _ => return Err(Error::input("invalid instruction").at(idx, line, line)),
             };
             let mut parts = line[4..].split(", ");
-            let reg = parse_register(parts.next().unwrap())?;
-            let offset = parts
-                .next()
-                .ok_or(Error::Input("expected offset"))?
-                .parse()?;
+            let reg = register(parts.next().unwrap())?;
+            let offset = offset(
+                parts
+                    .next()
+                    .ok_or_else(|| Error::input("expected offset").at(idx, line, &line[line.len()..]))?,
+            )?;
             Ok(constructor(reg, offset))
         })
         .collect::<Result<_>>()
 }
+
+impl InputGenerator for Solver {
+    /// The number of instructions that compute the start values.
+    const DEFAULT_SIZE: usize = 40;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // Like the real programs, the program computes a start value for a,
+        // one when a starts at 0 and another one when it starts at 1, and
+        // then counts the steps of the Collatz sequence from there into b. The
+        // start values are at least 1, or the sequence wouldn't end.
+        fn prelude(rng: &mut Rng, mut a: usize, count: usize) -> Vec<String> {
+            let mut lines = Vec::new();
+            for _ in 0..count {
+                if a == 0 || a > 1 << 40 || rng.chance(1, 2) {
+                    lines.push("inc a".to_owned());
+                    a += 1;
+                } else {
+                    lines.push("tpl a".to_owned());
+                    a *= 3;
+                }
+            }
+            lines
+        }
+        let from_0 = prelude(rng, 0, (size / 2).max(1));
+        let from_1 = prelude(rng, 1, size / 2);
+
+        let mut lines = vec![format!("jio a, +{}", from_0.len() + 2)];
+        lines.extend(from_0);
+        lines.push(format!("jmp +{}", from_1.len() + 1));
+        lines.extend(from_1);
+        lines.extend(
+            ["jio a, +8", "inc b", "jie a, +4", "tpl a", "inc a", "jmp +2", "hlf a", "jmp -7"]
+                .iter()
+                .map(|line| line.to_string()),
+        );
+        lines.join("\n")
+    }
+}
@@ -1,10 +1,12 @@
 day!(
     day24,
-    "https://adventofcode.com/2015/day/24/input",
+    24,
+    parse_weights -> Vec<u64>,
     part1,
     part2
 );
 
+use crate::generate::{InputGenerator, Rng};
 use itertools::Itertools;
 use std::iter::{FromIterator, Iterator};
 
@@ -48,16 +50,27 @@ fn compute_quantum_entanglement(slice: &[u64]) -> u64 {
     slice.iter().fold(1, |acc, nr| acc * (*nr))
 }
 
-fn solve(input: String, buckets: u64) -> Result<u64> {
+/// Parses the package weights, heaviest first.
+pub fn parse_weights(input: &str) -> Result<Vec<u64>> {
     let mut nrs = input
         .lines()
         .map(|nr| Ok(nr.parse()?))
         .collect::<Result<Vec<u64>>>()?;
     nrs.sort_unstable_by(|a, b| b.cmp(&a));
+    Ok(nrs)
+}
 
+/// The quantum entanglement of the first group, when the packages are split
+/// into `buckets` groups of equal weight.
+pub fn solve(nrs: &[u64], buckets: u64) -> Result<u64> {
     let total_weight = nrs.iter().sum::<u64>();
     if total_weight % buckets != 0 {
-        return Err(Error::Input("sum of the numbers has to be a multiple of 3"));
+        // Input error messages are static, the puzzle only uses 3 and 4 groups
+        return Err(Error::input(match buckets {
+            3 => "sum of the numbers has to be a multiple of 3",
+            4 => "sum of the numbers has to be a multiple of 4",
+            _ => "sum of the numbers has to be a multiple of the number of groups",
+        }));
     }
 
     let mut combinations = Vec::new();
@@ -75,7 +88,7 @@ fn solve(input: String, buckets: u64) -> Result<u64> {
         fn backtrack(
             stack: &mut Vec<u64>,
             combinations: &mut Vec<Vec<u64>>,
-            nrs: &Vec<u64>,
+            nrs: &[u64],
             weight_per_bucket: u64,
             number_count: usize,
             start_idx: usize,
@@ -102,7 +115,7 @@ fn solve(input: String, buckets: u64) -> Result<u64> {
         backtrack(
             &mut backtrack_stack,
             &mut combinations,
-            &nrs,
+            nrs,
             weight_per_bucket,
             number_count,
             0,
@@ -144,19 +157,60 @@ fn solve(input: String, buckets: u64) -> Result<u64> {
         }
     }
 
-    return Err(Error::Input("no solution found"));
+    Err(Error::input("no solution found"))
 }
 
-fn part1(input: String) -> Result<u64> {
-    solve(input, 3)
+fn part1(nrs: &[u64]) -> Result<u64> {
+    solve(nrs, 3)
+}
+fn part2(nrs: &[u64]) -> Result<u64> {
+    solve(nrs, 4)
 }
-fn part2(input: String) -> Result<u64> {
-    solve(input, 4)
+
+impl InputGenerator for Solver {
+    /// The number of packages, at least 24.
+    const DEFAULT_SIZE: usize = 29;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // Twelve groups of equal weight, which combine into three as well as
+        // four groups of equal weight. The last package of each group makes
+        // up its weight, so the other (distinct) weights are drawn first.
+        let count = size.max(24);
+        loop {
+            let mut counts = [2; 12];
+            for _ in 24..count {
+                counts[rng.below(12)] += 1;
+            }
+            let mut pool = (1..count as u64 * 4).collect::<Vec<_>>();
+            rng.shuffle(&mut pool);
+            let mut pool = pool.into_iter();
+            let groups = counts
+                .iter()
+                .map(|&n| pool.by_ref().take(n - 1).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let sums = groups.iter().map(|group| group.iter().sum::<u64>()).collect::<Vec<_>>();
+            if (1..sums.len()).any(|i| sums[..i].contains(&sums[i])) {
+                // The last packages would have the same weight
+                continue;
+            }
+            // Heavier than any package drawn from the pool
+            let weight = sums.iter().max().unwrap() + count as u64 * 4;
+            let mut weights = groups
+                .into_iter()
+                .zip(&sums)
+                .flat_map(|(mut group, sum)| {
+                    group.push(weight - sum);
+                    group
+                })
+                .collect::<Vec<_>>();
+            rng.shuffle(&mut weights);
+            return weights.iter().map(|weight| weight.to_string()).collect::<Vec<_>>().join("\n");
+        }
+    }
 }
 
 #[test]
 fn day24_test() {
-    assert_results!(part1, "\
+    assert_results!(parse_weights, part1, "\
 1
 2
 3
@@ -167,4 +221,8 @@ fn day24_test() {
 9
 10
 11" => 99);
+    assert_eq!(
+        solve(&[1, 2, 3, 4, 5], 4).err().unwrap().to_string(),
+        "invalid input: sum of the numbers has to be a multiple of 4"
+    );
 }
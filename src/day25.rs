@@ -1,16 +1,18 @@
 day!(
     day25,
-    "https://adventofcode.com/2015/day/25/input",
+    25,
+    parse_input -> (u64, u64),
     part1,
     !
 );
 
+use crate::generate::{InputGenerator, Rng};
+
 fn get_grid_index(row: u64, column: u64) -> u64 {
     (row + column + 1) * (row + column) / 2 + column
 }
 
-fn part1(input: String) -> Result<u64> {
-    let (row, column) = parse_input(input)?;
+fn part1(&(row, column): &(u64, u64)) -> Result<u64> {
     let (row, column) = (row - 1, column - 1); // Make 0 based
 
     let idx = get_grid_index(row, column) + 1;
@@ -22,28 +24,40 @@ fn part1(input: String) -> Result<u64> {
     Ok(nr)
 }
 
-fn parse_input(input: String) -> Result<(u64, u64)> {
+pub fn parse_input(input: &str) -> Result<(u64, u64)> {
     const PREFIX: &'static str = "To continue, please consult the code grid in the manual.  Enter the code at row ";
     const MID: &'static str = ", column ";
     const SUFFIX: &'static str = ".";
 
     if input.len() < PREFIX.len() + MID.len() + SUFFIX.len() + 2 {
-        return Err(Error::Input("input too short"));
+        return Err(Error::input("input too short"));
     }
 
     if &input[0..PREFIX.len()] != PREFIX {
-        return Err(Error::Input("invalid prefix"));
+        return Err(Error::input("invalid prefix"));
     }
     let input = &input[PREFIX.len()..];
-    let idx = input.find(MID).ok_or(Error::Input("no mid found"))?;
+    let idx = input.find(MID).ok_or(Error::input("no mid found"))?;
     let row = input[0..idx].parse()?;
     let input = &input[idx + MID.len()..];
-    let idx = input.find(SUFFIX).ok_or(Error::Input("no suffix found"))?;
+    let idx = input.find(SUFFIX).ok_or(Error::input("no suffix found"))?;
     let column = input[0..idx].parse()?;
     let input = &input[idx + SUFFIX.len()..];
     if input.len() != 0 {
-        return Err(Error::Input("input not empty"));
+        return Err(Error::input("input not empty"));
     }
 
     Ok((row, column))
-}
\ No newline at end of file
+}
+
+impl InputGenerator for Solver {
+    /// The largest row and column.
+    const DEFAULT_SIZE: usize = 3000;
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        format!(
+            "To continue, please consult the code grid in the manual.  Enter the code at row {}, column {}.",
+            rng.range(1..size.max(1) + 1),
+            rng.range(1..size.max(1) + 1)
+        )
+    }
+}
@@ -0,0 +1,254 @@
+//! Renders the `<article>` elements of a puzzle page as terminal text.
+
+use crate::{Error, Result};
+use colored::*;
+use regex::Regex;
+
+/// Paragraphs are wrapped at this many columns, code blocks are left as is.
+const WIDTH: usize = 80;
+
+/// Whether the page contains the description of part 2, which is only
+/// included once part 1 is solved.
+pub fn has_part2(html: &str) -> bool {
+    html.matches("<article").count() >= 2
+}
+
+pub fn render(html: &str) -> Result<String> {
+    lazy_static! {
+        static ref ARTICLE: Regex = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    }
+    let mut renderer = Renderer::default();
+    for captures in ARTICLE.captures_iter(html) {
+        renderer.block_break();
+        renderer.render(&captures[1]);
+    }
+    if renderer.out.is_empty() {
+        return Err(Error::UnrecognizedResponse);
+    }
+    let mut out = renderer.out.trim_end().to_owned();
+    out.push('\n');
+    Ok(out)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Heading,
+    Emphasis,
+    /// The stars awarded for solving a part.
+    Star,
+    Code,
+    Link,
+}
+
+#[derive(Default)]
+struct Renderer {
+    out: String,
+    /// The visible width of the current line, without escape codes.
+    column: usize,
+    /// Whether whitespace was skipped since the last word.
+    space: bool,
+    indent: usize,
+    styles: Vec<Style>,
+    in_pre: bool,
+}
+
+impl Renderer {
+    fn render(&mut self, html: &str) {
+        let mut rest = html;
+        while let Some(start) = rest.find('<') {
+            self.text(&decode_entities(&rest[..start]));
+            let end = match rest[start..].find('>') {
+                Some(end) => start + end,
+                None => return,
+            };
+            self.tag(&rest[start + 1..end]);
+            rest = &rest[end + 1..];
+        }
+        self.text(&decode_entities(rest));
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let is_closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/');
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        let style = match name.as_str() {
+            "h2" => Some(Style::Heading),
+            "em" if tag.contains("class=\"star\"") => Some(Style::Star),
+            "em" => Some(Style::Emphasis),
+            "code" => Some(Style::Code),
+            "a" => Some(Style::Link),
+            _ => None,
+        };
+        if let Some(style) = style {
+            if is_closing {
+                if let Some(idx) = self.styles.iter().rposition(|&s| s == style) {
+                    self.styles.remove(idx);
+                }
+            } else {
+                self.styles.push(style);
+            }
+        }
+
+        match (name.as_str(), is_closing) {
+            ("h2", _) | ("p", _) | ("ul", _) => self.block_break(),
+            ("pre", closing) => {
+                self.block_break();
+                self.in_pre = !closing;
+            }
+            ("li", false) => {
+                self.line_break();
+                self.out.push_str("  - ");
+                self.column = 4;
+                self.indent = 4;
+            }
+            ("li", true) => {
+                self.line_break();
+                self.indent = 0;
+            }
+            ("br", _) => self.line_break(),
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.in_pre {
+            for (idx, line) in text.split('\n').enumerate() {
+                if idx > 0 {
+                    self.line_break();
+                }
+                if !line.is_empty() {
+                    if self.column == 0 {
+                        self.write("    ", 4);
+                    }
+                    let painted = self.paint(line);
+                    self.write(&painted, line.chars().count());
+                }
+            }
+            return;
+        }
+
+        let mut space = self.space || text.starts_with(char::is_whitespace);
+        for word in text.split_whitespace() {
+            let width = word.chars().count();
+            if space && self.column > self.indent {
+                if self.column + 1 + width > WIDTH {
+                    self.line_break();
+                } else {
+                    self.write(" ", 1);
+                }
+            }
+            let painted = self.paint(word);
+            self.write(&painted, width);
+            space = true;
+        }
+        // A word that follows a word in another style without whitespace in
+        // between (such as punctuation after emphasis) is glued to it
+        self.space = if text.ends_with(char::is_whitespace) {
+            true
+        } else {
+            text.trim().is_empty() && space
+        };
+    }
+
+    fn paint(&self, text: &str) -> String {
+        let mut painted = text.normal();
+        for style in &self.styles {
+            painted = match style {
+                Style::Heading => painted.bold(),
+                Style::Emphasis => painted.bright_white().bold(),
+                Style::Star => painted.bright_yellow(),
+                Style::Code => painted.bright_green(),
+                Style::Link => painted.underline(),
+            };
+        }
+        painted.to_string()
+    }
+
+    fn write(&mut self, text: &str, width: usize) {
+        if self.column == 0 && self.indent > 0 {
+            self.out.push_str(&" ".repeat(self.indent));
+            self.column = self.indent;
+        }
+        self.out.push_str(text);
+        self.column += width;
+    }
+
+    fn line_break(&mut self) {
+        if self.column > 0 {
+            self.out.push('\n');
+            self.column = 0;
+        }
+        self.space = false;
+    }
+
+    /// Ends the current block with an empty line.
+    fn block_break(&mut self) {
+        self.line_break();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+}
+
+pub fn decode_entities(text: &str) -> String {
+    lazy_static! {
+        static ref ENTITY: Regex = Regex::new(r"&(#x[0-9a-fA-F]+|#[0-9]+|[a-z]+);").unwrap();
+    }
+    ENTITY
+        .replace_all(text, |captures: &regex::Captures| {
+            let entity = &captures[1];
+            let decoded = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16).ok().and_then(char::from_u32),
+                _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+                _ => None,
+            };
+            decoded.map_or_else(|| captures[0].to_owned(), |c| c.to_string())
+        })
+        .into_owned()
+}
+
+#[test]
+fn render_test() {
+    colored::control::set_override(false);
+    let html = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Not Quite Lisp ---</h2><p>Santa is trying to deliver presents in a large apartment building, but he can't find the right floor - the directions he got are a little <em>confusing</em>. He starts on the ground floor (floor <code>0</code>) and then follows the instructions one character at a time.</p>
+<p>For example:</p>
+<ul>
+<li><code>(())</code> and <code>()()</code> both result in floor <code>0</code>.</li>
+</ul>
+<pre><code>a &lt;-&gt; b
+c
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>232</code>.</p>
+</main>"#;
+    assert!(!has_part2(html));
+    assert_eq!(
+        render(html).unwrap(),
+        "--- Day 1: Not Quite Lisp ---
+
+Santa is trying to deliver presents in a large apartment building, but he can't
+find the right floor - the directions he got are a little confusing. He starts
+on the ground floor (floor 0) and then follows the instructions one character at
+a time.
+
+For example:
+
+  - (()) and ()() both result in floor 0.
+
+    a <-> b
+    c
+"
+    );
+    assert!(render("<html></html>").is_err());
+}
@@ -9,16 +9,176 @@ pub enum Error {
     ParseInt(::std::num::ParseIntError),
 
     DayDoesNotExist(String),
+    PartDoesNotExist(String, usize),
+    MissingInputFile(::std::path::PathBuf),
+    /// A day has no parameter with this name, with the names it does have.
+    UnknownParam(String, String, Vec<&'static str>),
+    /// A parameter (as `dayXX.name`) was given a value that isn't of its type.
+    InvalidParam(String, String, &'static str),
     MissingSessionToken,
-    InvalidSessionToken(::reqwest::StatusCode),
-    Input(&'static str),
+    InvalidConfig(String),
+    InvalidCache(String),
+    FetchDisabled(String),
+    SessionExpired(::reqwest::StatusCode),
+    PuzzleNotFound(String),
+    /// The day's puzzle is still locked, with the time until it unlocks.
+    PuzzleLocked(&'static str, ::std::time::Duration),
+    ServerError(::reqwest::StatusCode),
+    UnexpectedStatus(::reqwest::StatusCode),
+    UnrecognizedResponse,
+    Input(InputError),
+    /// Parsing the input panicked, with the message and location.
+    ParsePanic(String),
+    /// The part was cancelled because it exceeded its timeout.
+    Cancelled,
+}
+
+/// A problem with the puzzle input, optionally pointing at where it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    pub message: &'static str,
+    pub day: Option<&'static str>,
+    pub location: Option<Location>,
+    /// Other (one-based) lines with the same problem.
+    pub other_lines: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// One-based line number.
+    pub line: usize,
+    /// One-based column, in characters.
+    pub column: usize,
+    pub snippet: String,
+}
+
+impl Error {
+    pub fn input(message: &'static str) -> Error {
+        Error::Input(InputError {
+            message,
+            day: None,
+            location: None,
+            other_lines: Vec::new(),
+        })
+    }
+
+    /// Points an input error at `part`, which must be a slice of `line`, the
+    /// line at (zero-based) `line_index` of the input. Other errors are
+    /// returned unchanged.
+    pub fn at(self, line_index: usize, line: &str, part: &str) -> Error {
+        match self {
+            Error::Input(mut e) => {
+                let offset = (part.as_ptr() as usize)
+                    .checked_sub(line.as_ptr() as usize)
+                    .filter(|&offset| offset <= line.len())
+                    .unwrap_or(0);
+                e.location = Some(Location {
+                    line: line_index + 1,
+                    column: line[..offset].chars().count() + 1,
+                    snippet: line.to_owned(),
+                });
+                Error::Input(e)
+            }
+            e => e,
+        }
+    }
+
+    /// Adds the other (zero-based) line indices with the same problem to an
+    /// input error. Other errors are returned unchanged.
+    pub fn also_at(self, line_indices: &[usize]) -> Error {
+        match self {
+            Error::Input(mut e) => {
+                e.other_lines.extend(line_indices.iter().map(|idx| idx + 1));
+                Error::Input(e)
+            }
+            e => e,
+        }
+    }
+
+    /// Attributes an input error to a day, unless it already is.
+    pub fn with_day(self, day: &'static str) -> Error {
+        match self {
+            Error::Input(mut e) => {
+                e.day = e.day.or(Some(day));
+                Error::Input(e)
+            }
+            e => e,
+        }
+    }
+}
+
+impl ::std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match self.day {
+            Some(day) => write!(f, "{}: {}", day, self.message)?,
+            None => write!(f, "invalid input: {}", self.message)?,
+        }
+        if let Some(location) = &self.location {
+            // day23: invalid register at line 5, column 5
+            //     5 | inc c
+            //       |     ^
+            let gutter = location.line.to_string();
+            write!(f, " at line {}, column {}", location.line, location.column)?;
+            if !self.other_lines.is_empty() {
+                let lines = self.other_lines.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, " (also {} {})", if lines.len() == 1 { "line" } else { "lines" }, lines.join(", "))?;
+            }
+            write!(f, "\n    {} | {}", gutter, location.snippet)?;
+            write!(
+                f,
+                "\n    {} | {}^",
+                " ".repeat(gutter.len()),
+                " ".repeat(location.column - 1)
+            )?;
+        }
+        Ok(())
+    }
 }
 
 impl ::std::error::Error for Error {}
 
 impl ::std::fmt::Display for Error {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-        <Self as ::std::fmt::Debug>::fmt(self, f)
+        match self {
+            Error::Network(e) if e.is_timeout() => write!(f, "request timed out: {}", e),
+            Error::Network(e) if e.is_connect() => write!(f, "could not connect to the server: {}", e),
+            Error::Network(e) => write!(f, "network error: {}", e),
+            Error::MissingSessionToken => write!(f, "no session token found, create token.txt or set AOC_SESSION"),
+            Error::InvalidConfig(message) => write!(f, "invalid configuration: {}", message),
+            Error::InvalidCache(message) => write!(f, "invalid input cache: {}", message),
+            Error::FetchDisabled(url) => write!(f, "{} is not cached and fetching is disabled", url),
+            Error::SessionExpired(status) => write!(
+                f,
+                "the server rejected the session token ({}), log in again and update the token",
+                status
+            ),
+            Error::PuzzleNotFound(url) => write!(f, "puzzle not found at {}, is it unlocked yet?", url),
+            Error::PuzzleLocked(day, remaining) => write!(
+                f,
+                "{} unlocks in {}, run with --wait to wait for it",
+                day,
+                crate::release::format_countdown(*remaining)
+            ),
+            Error::ServerError(status) => write!(f, "the server failed to respond ({}), try again later", status),
+            Error::UnexpectedStatus(status) => write!(f, "unexpected response from the server ({})", status),
+            Error::UnrecognizedResponse => write!(f, "unrecognized response from the server"),
+            Error::Bincode(e) => write!(f, "could not serialize data: {}", e),
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+            Error::Fmt(e) => write!(f, "formatting error: {}", e),
+            Error::Json(e) => write!(f, "invalid JSON: {}", e),
+            Error::ParseInt(e) => write!(f, "invalid number in input: {}", e),
+            Error::DayDoesNotExist(day) => write!(f, "{} does not exist", day),
+            Error::PartDoesNotExist(day, part) => write!(f, "{} has no part{}", day, part),
+            Error::UnknownParam(day, name, params) if params.is_empty() => write!(f, "{} has no parameter {}", day, name),
+            Error::UnknownParam(day, name, params) => {
+                write!(f, "{} has no parameter {}, expected one of {}", day, name, params.join(", "))
+            }
+            Error::InvalidParam(param, value, ty) => write!(f, "invalid value for {}: {} is not a {}", param, value, ty),
+            Error::MissingInputFile(path) => write!(f, "input file {} does not exist", path.display()),
+            Error::Input(e) => e.fmt(f),
+            Error::ParsePanic(message) => write!(f, "parsing the input panicked: {}", message),
+            Error::Cancelled => write!(f, "cancelled after exceeding the timeout"),
+        }
     }
 }
 
@@ -52,3 +212,17 @@ impl From<::std::num::ParseIntError> for Error {
         Error::ParseInt(e)
     }
 }
+
+#[test]
+fn input_error_test() {
+    let line = "inc c";
+    let error = Error::input("invalid register").at(4, line, &line[4..]).with_day("day23");
+    assert_eq!(
+        error.to_string(),
+        "day23: invalid register at line 5, column 5\n    5 | inc c\n      |     ^"
+    );
+    assert_eq!(
+        Error::input("input too short").to_string(),
+        "invalid input: input too short"
+    );
+}
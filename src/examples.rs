@@ -0,0 +1,158 @@
+//! A corpus of example inputs taken from the puzzle descriptions. Each
+//! example is stored as `dayXX/N.txt`, next to a `dayXX/N.expected` sidecar
+//! with the expected answers in the same `partN: answer` format as the answer
+//! store. The sidecar starts out without answers, and an example is only
+//! checked for the parts that were filled in by hand. Examples that use other
+//! puzzle constants than the real input set them with `param NAME=VALUE`
+//! lines in the sidecar.
+
+use crate::answers::Answers;
+use crate::describe::decode_entities;
+use crate::framework::trim_trailing_newlines;
+use crate::{Error, Result};
+use regex::Regex;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct Corpus {
+    dir: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct Example {
+    /// E.g. `day09/1`.
+    pub name: String,
+    pub input: String,
+    pub expected: Answers,
+    /// The parameters to run the example with, as `(name, value)`.
+    pub params: Vec<(String, String)>,
+}
+
+/// The contents of the `<pre><code>` blocks of a puzzle page, in order and
+/// without duplicates.
+pub fn extract(html: &str) -> Vec<String> {
+    lazy_static! {
+        static ref CODE_BLOCK: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+        static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+    }
+    let mut blocks: Vec<String> = Vec::new();
+    for captures in CODE_BLOCK.captures_iter(html) {
+        let block = trim_trailing_newlines(decode_entities(&TAG.replace_all(&captures[1], "")));
+        if !block.trim().is_empty() && !blocks.contains(&block) {
+            blocks.push(block);
+        }
+    }
+    blocks
+}
+
+impl Corpus {
+    pub fn new(dir: PathBuf) -> Corpus {
+        Corpus { dir }
+    }
+
+    /// Adds an example to a day, with an empty sidecar, unless the day already
+    /// has an example with the same input. Returns the path of the new
+    /// example.
+    pub fn add(&self, day: &str, input: &str) -> Result<Option<PathBuf>> {
+        let examples = self.load(day)?;
+        if examples.iter().any(|example| example.input == input) {
+            return Ok(None);
+        }
+        let dir = self.dir.join(day);
+        fs::create_dir_all(&dir)?;
+        let number = (1..).find(|n| !dir.join(format!("{}.txt", n)).exists()).unwrap();
+        let path = dir.join(format!("{}.txt", number));
+        fs::write(&path, format!("{}\n", input))?;
+        fs::write(
+            dir.join(format!("{}.expected", number)),
+            format!(
+                "# The expected answers for {}.txt, as 'partN: answer' lines, and its\n\
+                 # parameters, as 'param NAME=VALUE' lines\n",
+                number
+            ),
+        )?;
+        Ok(Some(path))
+    }
+
+    /// The examples of a day, in numerical order.
+    pub fn load(&self, day: &str) -> Result<Vec<Example>> {
+        let entries = match fs::read_dir(self.dir.join(day)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut numbers = Vec::new();
+        for entry in entries {
+            let file_name = entry?.file_name();
+            let number = file_name
+                .to_str()
+                .and_then(|x| x.strip_suffix(".txt"))
+                .and_then(|x| x.parse::<u32>().ok());
+            if let Some(number) = number {
+                numbers.push(number);
+            }
+        }
+        numbers.sort_unstable();
+
+        let dir = self.dir.join(day);
+        let mut examples = Vec::new();
+        for number in numbers {
+            let input = fs::read_to_string(dir.join(format!("{}.txt", number)))?;
+            let sidecar = match fs::read_to_string(dir.join(format!("{}.expected", number))) {
+                Ok(sidecar) => sidecar,
+                Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+                Err(e) => return Err(e.into()),
+            };
+            let mut answers = String::new();
+            let mut params = Vec::new();
+            for line in sidecar.lines() {
+                match line.strip_prefix("param ") {
+                    Some(param) => {
+                        let (name, value) = param
+                            .split_once('=')
+                            .ok_or(Error::input("expected 'param NAME=VALUE'"))?;
+                        params.push((name.trim().to_owned(), value.trim().to_owned()));
+                    }
+                    None => {
+                        answers.push_str(line);
+                        answers.push('\n');
+                    }
+                }
+            }
+            examples.push(Example {
+                name: format!("{}/{}", day, number),
+                input: trim_trailing_newlines(input),
+                expected: answers.parse()?,
+                params,
+            });
+        }
+        Ok(examples)
+    }
+}
+
+#[test]
+fn corpus_test() {
+    let html = "<article><p>For example:</p><pre><code>London to Dublin = <em>464</em>
+Dublin to Belfast = 141
+</code></pre><p>Same again:</p><pre><code>London to Dublin = 464
+Dublin to Belfast = 141</code></pre><pre><code>a -&gt; b</code></pre></article>";
+    let blocks = extract(html);
+    assert_eq!(blocks, ["London to Dublin = 464\nDublin to Belfast = 141", "a -> b"]);
+
+    let corpus = Corpus::new(crate::mock_server::temp_dir("corpus"));
+    let path = corpus.add("day09", &blocks[0]).unwrap().unwrap();
+    assert!(path.ends_with("day09/1.txt"));
+    assert_eq!(corpus.add("day09", &blocks[0]).unwrap(), None);
+    assert!(corpus.add("day09", &blocks[1]).unwrap().unwrap().ends_with("day09/2.txt"));
+
+    fs::write(corpus.dir.join("day09/1.expected"), "# Edited\npart1: 605\nparam cities = 3\n").unwrap();
+    let examples = corpus.load("day09").unwrap();
+    assert_eq!(examples.len(), 2);
+    assert_eq!(examples[0].name, "day09/1");
+    assert_eq!(examples[0].input, blocks[0]);
+    assert_eq!(examples[0].expected.get(1), Some("605"));
+    assert_eq!(examples[0].params, [("cities".to_owned(), "3".to_owned())]);
+    assert_eq!(examples[1].expected.get(1), None);
+}
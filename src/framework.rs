@@ -1,23 +1,100 @@
 macro_rules! day {
-    ($name:tt, $url:tt, $part1:tt, $part2:tt) => {
+    ($name:tt, $number:tt, $part1:tt, $part2:tt $(, timeout: $timeout:expr)? $(, params: $params:tt)?) => {
+        day!(
+            $name,
+            $number,
+            (|input: &str| -> Result<String> { Ok(input.to_owned()) }) -> String,
+            $part1,
+            $part2
+            $(, timeout: $timeout)?
+            $(, params: $params)?
+        );
+    };
+    (
+        $name:tt,
+        $number:tt,
+        $parse:tt -> $parsed:ty,
+        $part1:tt,
+        $part2:tt
+        $(, timeout: $timeout:expr)?
+        $(, params: { $($(#[$meta:meta])* $param:ident: $type:ty = $default:expr),* $(,)? })?
+    ) => {
         #[allow(unused_imports)]
         use crate::{Error, Result};
-        pub(crate) fn register_day(fw: &mut crate::framework::Framework) {
-            fw.register_day(
-                stringify!($name),
-                $url,
-                day_callback!($part1),
-                day_callback!($part2),
-            );
+        /// The solution of this day, to register with a framework or to call
+        /// directly through the `Solution` trait.
+        pub struct Solver;
+        impl crate::framework::Solution for Solver {
+            type Parsed = $parsed;
+            type Params = Params;
+            const HAS_PART2: bool = day_has_part!($part2);
+            $(const TIMEOUT: Option<std::time::Duration> = Some(std::time::Duration::from_secs($timeout));)?
+            fn parse(input: &str, options: &crate::framework::ParseOptions) -> Result<$parsed> {
+                crate::framework::Parser::call(&$parse, input, options)
+                    .map_err(|e: Error| e.with_day(stringify!($name)))
+            }
+            fn part1(parsed: &$parsed, params: &Params) -> Result<String> {
+                day_callback!($name, 1, $part1, parsed, params, [$($($param)*)?])
+            }
+            fn part2(parsed: &$parsed, params: &Params) -> Result<String> {
+                day_callback!($name, 2, $part2, parsed, params, [$($($param)*)?])
+            }
+        }
+        /// The puzzle constants of this day, which can be overridden with
+        /// `--param dayXX.name=value`.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Params {
+            $($($(#[$meta])* pub $param: $type,)*)?
+        }
+        impl Default for Params {
+            fn default() -> Params {
+                Params {
+                    $($($param: $default,)*)?
+                }
+            }
+        }
+        impl crate::framework::Params for Params {
+            const LIST: &'static [(&'static str, &'static str, &'static str)] =
+                &[$($((stringify!($param), stringify!($type), stringify!($default)),)*)?];
+            fn set(&mut self, name: &str, value: &str) -> bool {
+                $($(
+                    if name == stringify!($param) {
+                        return value.parse().map(|value| self.$param = value).is_ok();
+                    }
+                )*)?
+                let _ = (name, value);
+                false
+            }
+        }
+        pub fn register_day(fw: &mut crate::framework::Framework) {
+            fw.register_day::<Solver>(stringify!($name), $number);
         }
     };
 }
-macro_rules! day_callback {
+macro_rules! day_has_part {
     (!) => {
-        None
+        false
     };
     ($callback:ident) => {
-        Some((|input| $callback(input).map(|x| x.to_string())) as fn(String) -> Result<String>)
+        true
+    };
+}
+/// Calls a part, passing the parameters along only if the day declares any.
+macro_rules! day_callback {
+    ($name:tt, $part:tt, !, $parsed:ident, $params:ident, [$($declared:tt)*]) => {{
+        let _ = ($parsed, $params);
+        Err(Error::PartDoesNotExist(stringify!($name).to_owned(), $part))
+    }};
+    ($name:tt, $part:tt, $callback:ident, $parsed:ident, $params:ident, []) => {{
+        let _ = $params;
+        $callback($parsed)
+            .map(|x| x.to_string())
+            .map_err(|e| e.with_day(stringify!($name)))
+    }};
+    ($name:tt, $part:tt, $callback:ident, $parsed:ident, $params:ident, [$($declared:tt)+]) => {
+        $callback($parsed, $params)
+            .map(|x| x.to_string())
+            .map_err(|e| e.with_day(stringify!($name)))
     };
 }
 #[allow(unused_macros)]
@@ -26,134 +103,1518 @@ macro_rules! assert_results {
     ($fn:ident, $($input:tt => $expected:expr),+$(,)*) => {
         $(
             assert_eq!(
-                $fn($input.to_owned()).expect("function should run without error"),
+                $fn($input).expect("function should run without error"),
+                $expected
+            );
+        )+
+    };
+    ($parse:ident, $fn:ident, $($input:tt => $expected:expr),+$(,)*) => {
+        $(
+            assert_eq!(
+                $fn(&$parse($input).expect("input should parse"))
+                    .expect("function should run without error"),
                 $expected
             );
         )+
     };
 }
 
+use crate::answers::{Answers, Verdict};
+use crate::bench::Stats;
+use crate::cache::InputCache;
+use crate::config::Config;
+use crate::describe;
+use crate::examples::{self, Corpus};
+use crate::output::{Format, Status};
+use crate::rate_limit::RateLimiter;
+use crate::release::{self, Clock, SystemClock};
+use crate::submit::{self, Outcome, Submissions};
 use crate::{Error, Result};
-use reqwest::{blocking::Client, StatusCode};
+use reqwest::{
+    blocking::{Client, Response},
+    StatusCode,
+};
 use colored::*;
-use std::collections::{BTreeMap, HashMap};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, Once};
+use std::thread;
 use std::time::{Duration, Instant};
 
+pub const YEAR: u32 = 2015;
+
+/// A day's solution. The input is parsed once, and both parts are computed
+/// from the parsed input, so that parsing can be timed separately and the
+/// parsed input can be shared by the parts, tests and benchmarks.
+///
+/// Days implement this through the `day!` macro.
+pub trait Solution {
+    type Parsed: Any + Send + Sync;
+    type Params: Params;
+    /// Whether the day has a second part, which the last day doesn't.
+    const HAS_PART2: bool = true;
+    /// How long a part may run before it's cancelled, unless overridden on
+    /// the command line.
+    const TIMEOUT: Option<Duration> = None;
+    fn parse(input: &str, options: &ParseOptions) -> Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed, params: &Self::Params) -> Result<String>;
+    fn part2(parsed: &Self::Parsed, params: &Self::Params) -> Result<String>;
+}
+
+/// How the input is parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Skip input lines that don't parse instead of failing, see
+    /// [`try_parse_lines`].
+    pub lenient: bool,
+}
+
+/// The parser of a day, which takes the parse options if it needs them. `Args`
+/// tells the two kinds of parsers apart.
+pub trait Parser<Args, T> {
+    fn call(&self, input: &str, options: &ParseOptions) -> Result<T>;
+}
+
+impl<T, F: Fn(&str) -> Result<T>> Parser<(), T> for F {
+    fn call(&self, input: &str, _: &ParseOptions) -> Result<T> {
+        self(input)
+    }
+}
+
+impl<T, F: Fn(&str, &ParseOptions) -> Result<T>> Parser<ParseOptions, T> for F {
+    fn call(&self, input: &str, options: &ParseOptions) -> Result<T> {
+        self(input, options)
+    }
+}
+
+/// The puzzle constants of a day, such as how many steps to simulate, so that
+/// the examples and what-if experiments can run with other values. Days
+/// declare them in the `day!` macro as `params: { name: type = default }`.
+pub trait Params: Default + Clone + Any + Send + Sync {
+    /// The name, type and default value of every parameter.
+    const LIST: &'static [(&'static str, &'static str, &'static str)];
+    /// Overrides a parameter, returning false if it doesn't exist or if the
+    /// value doesn't parse as its type.
+    fn set(&mut self, name: &str, value: &str) -> bool;
+}
+
+impl Params for () {
+    const LIST: &'static [(&'static str, &'static str, &'static str)] = &[];
+    fn set(&mut self, _: &str, _: &str) -> bool {
+        false
+    }
+}
+
+/// The parsed input of any day, along with the parameters to run it with.
+type Parsed = Box<dyn Any + Send + Sync>;
+
+/// The parameters of any day.
+type ErasedParams = Arc<dyn Any + Send + Sync>;
+
+/// Computes the answer to a part from the parsed input.
+type PartFn<P> = fn(&P) -> Result<String>;
+
+/// Creates the parameters of a day from its name and `(name, value)`
+/// overrides.
+type MakeParamsFn = fn(&str, &[(&str, &str)]) -> Result<ErasedParams>;
+
+/// The result of running a single part, with errors and panics caught so that
+/// the remaining parts and days can still run.
+#[derive(Debug)]
+pub enum PartResult {
+    Answer(String),
+    Error(Error),
+    Panic(String),
+    /// The part was cancelled after running for the given duration.
+    Timeout(Duration),
+}
+
+/// A part that ran, along with how long it took.
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: usize,
+    pub result: PartResult,
+    pub duration: Duration,
+}
+
+/// The parts of a day that ran, and a hash identifying the input they ran on.
+#[derive(Debug)]
+pub struct DayRun {
+    pub input_hash: String,
+    pub parts: Vec<PartRun>,
+}
+
+impl PartResult {
+    pub fn status(&self) -> Status {
+        match self {
+            PartResult::Answer(_) => Status::Ok,
+            PartResult::Error(_) => Status::Err,
+            PartResult::Panic(_) => Status::Panic,
+            PartResult::Timeout(_) => Status::Timeout,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Framework {
     days: BTreeMap<&'static str, Day>,
+    base_url: String,
     token: Option<String>,
-    input_cache: HashMap<String, String>,
-    no_fetch_before: Option<Instant>,
+    input_cache: InputCache,
+    /// The input read from stdin, once it has been read.
+    stdin_input: Option<String>,
+    /// The parameter overrides, as `(day, name, value)`.
+    params: Vec<(String, String, String)>,
+    rate_limiter: RateLimiter,
+    clock: Arc<dyn Clock>,
+    input_dir: PathBuf,
+    answer_dir: PathBuf,
+    examples_dir: PathBuf,
+    submissions_path: PathBuf,
+    max_retries: u32,
+    retry_delay: Duration,
+    options: Options,
 }
 
+/// Settings from the command line that affect how days are run.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Only run this part.
+    pub part: Option<usize>,
+    /// Read the input from this file instead of the usual sources, or from
+    /// stdin if it's `-`.
+    pub input_file: Option<PathBuf>,
+    /// Fail instead of downloading inputs that aren't available locally.
+    pub no_fetch: bool,
+    /// Wait for locked puzzles to unlock instead of failing.
+    pub wait: bool,
+    /// Only print the answers.
+    pub quiet: bool,
+    /// Skip input lines that don't parse instead of failing.
+    pub lenient: bool,
+    pub format: Format,
+    /// Number of threads to run days and parts on, one if zero.
+    pub jobs: usize,
+    /// Overrides the timeout of every part, zero disables timeouts.
+    pub timeout: Option<Duration>,
+}
+
+/// A registered day, with the type of its parsed input erased.
 #[derive(Clone)]
 struct Day {
     name: &'static str,
-    url: &'static str,
-    part1: Option<fn(String) -> Result<String>>,
-    part2: Option<fn(String) -> Result<String>>,
+    number: u32,
+    parse: fn(&str, &ParseOptions, &ErasedParams) -> Result<Parsed>,
+    part1: Option<PartFn<Parsed>>,
+    part2: Option<PartFn<Parsed>>,
+    timeout: Option<Duration>,
+    params: ErasedParams,
+    make_params: MakeParamsFn,
+}
+
+fn parse_erased<S: Solution>(input: &str, options: &ParseOptions, params: &ErasedParams) -> Result<Parsed> {
+    let params: &S::Params = params.downcast_ref().expect("parameters of another day");
+    Ok(Box::new((S::parse(input, options)?, params.clone())))
+}
+
+fn part1_erased<S: Solution>(parsed: &Parsed) -> Result<String> {
+    let (parsed, params): &(S::Parsed, S::Params) = parsed.downcast_ref().expect("input parsed by another day");
+    S::part1(parsed, params)
+}
+
+fn part2_erased<S: Solution>(parsed: &Parsed) -> Result<String> {
+    let (parsed, params): &(S::Parsed, S::Params) = parsed.downcast_ref().expect("input parsed by another day");
+    S::part2(parsed, params)
+}
+
+/// The default parameters of a day with the given `(name, value)` overrides.
+fn make_params_erased<S: Solution>(day: &str, overrides: &[(&str, &str)]) -> Result<ErasedParams> {
+    let mut params = S::Params::default();
+    for &(name, value) in overrides {
+        let list = <S::Params as Params>::LIST;
+        let (_, ty, _) = list.iter().find(|param| param.0 == name).ok_or_else(|| {
+            Error::UnknownParam(day.to_owned(), name.to_owned(), list.iter().map(|param| param.0).collect())
+        })?;
+        if !params.set(name, value) {
+            return Err(Error::InvalidParam(format!("{}.{}", day, name), value.to_owned(), ty));
+        }
+    }
+    Ok(Arc::new(params))
 }
 
 impl Framework {
-    pub fn new() -> Framework {
-        use std::fs::{read, read_to_string};
-        let token = read_to_string("token.txt").ok();
-        let input_cache = read("cache.dat")
-            .ok()
-            .and_then(|x| ::bincode::deserialize::<HashMap<String, String>>(&x[..]).ok())
-            .unwrap_or(HashMap::new());
-        Framework {
+    pub fn new(config: &Config) -> Result<Framework> {
+        let mut input_cache = InputCache::open(config.profile_path("cache"))?;
+        input_cache.migrate(&config.profile_path("cache.dat"))?;
+        Ok(Framework {
             days: BTreeMap::new(),
-            token,
+            base_url: config.base_url.clone(),
+            token: config.token.clone(),
             input_cache,
-            no_fetch_before: None,
+            stdin_input: None,
+            params: Vec::new(),
+            rate_limiter: RateLimiter::new(config.path("last_fetch"), config.fetch_interval),
+            clock: Arc::new(SystemClock),
+            input_dir: config.profile_path("inputs"),
+            answer_dir: config.profile_path("answers"),
+            examples_dir: config.path("examples"),
+            submissions_path: config.profile_path("submissions.json"),
+            max_retries: 3,
+            retry_delay: Duration::from_secs(1),
+            options: Options::default(),
+        })
+    }
+
+    pub fn set_options(&mut self, options: Options) {
+        self.options = options;
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    fn parse_options(&self) -> ParseOptions {
+        ParseOptions {
+            lenient: self.options.lenient,
         }
     }
 
-    pub fn register_day(
-        &mut self,
-        name: &'static str,
-        url: &'static str,
-        part1: Option<fn(String) -> Result<String>>,
-        part2: Option<fn(String) -> Result<String>>,
-    ) -> bool {
+    /// Overrides the parameters of days, given as `(day, name, value)`. The
+    /// other parameters keep their default values.
+    pub fn set_params(&mut self, params: &[(String, String, String)]) -> Result<()> {
+        if let Some((day, _, _)) = params.iter().find(|(day, _, _)| !self.days.contains_key(day.as_str())) {
+            return Err(Error::DayDoesNotExist(day.clone()));
+        }
+        for day in self.days.values_mut() {
+            day.params = (day.make_params)(day.name, &param_overrides(params, day.name))?;
+        }
+        self.params = params.to_vec();
+        Ok(())
+    }
+
+    pub fn register_day<S: Solution>(&mut self, name: &'static str, number: u32) -> bool {
         if self.days.contains_key(&name) {
             return false;
         }
 
         let day = Day {
             name,
-            url,
-            part1,
-            part2,
+            number,
+            parse: parse_erased::<S>,
+            part1: Some(part1_erased::<S>),
+            part2: if S::HAS_PART2 { Some(part2_erased::<S>) } else { None },
+            timeout: S::TIMEOUT,
+            params: Arc::new(S::Params::default()),
+            make_params: make_params_erased::<S>,
         };
         self.days.insert(name, day);
 
         true
     }
 
-    fn cache_input(&mut self, client: &Client, url: &'static str) -> Result<()> {
-        if let Some(no_fetch_before) = self.no_fetch_before {
-            let now = Instant::now();
-            if now < no_fetch_before {
-                std::thread::sleep(no_fetch_before - now);
+    fn input_url(&self, day: &Day) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, YEAR, day.number)
+    }
+
+    /// The URL a day's input is downloaded from, which is also its key in the
+    /// input cache.
+    pub fn cache_key(&self, day: &str) -> Result<String> {
+        Ok(self.input_url(&self.get_day(day)?))
+    }
+
+    /// The example corpus in the `examples` directory.
+    pub fn corpus(&self) -> Corpus {
+        Corpus::new(self.examples_dir.clone())
+    }
+
+    pub fn input_cache(&mut self) -> &mut InputCache {
+        &mut self.input_cache
+    }
+
+    fn puzzle_url(&self, day: &Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day.number)
+    }
+
+    fn answer_url(&self, day: &Day) -> String {
+        format!("{}/{}/day/{}/answer", self.base_url, YEAR, day.number)
+    }
+
+    /// Sends a request, retrying transient failures (server errors, timeouts
+    /// and connection failures) with exponential backoff, and turns any
+    /// unsuccessful response into an error. Requests that aren't idempotent
+    /// are only retried when they couldn't connect, as the server may have
+    /// handled them otherwise.
+    fn send<F>(&self, url: &str, idempotent: bool, mut send: F) -> Result<Response>
+    where
+        F: FnMut() -> reqwest::Result<Response>,
+    {
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+        let response = loop {
+            let result = send();
+            let failure = match &result {
+                Ok(response) if idempotent && response.status().is_server_error() => response.status().to_string(),
+                Err(e) if (idempotent && e.is_timeout()) || e.is_connect() => e.to_string(),
+                _ => break result?,
+            };
+            if attempt >= self.max_retries {
+                break result?;
+            }
+            attempt += 1;
+            eprintln!(
+                "{}",
+                format!("request to {} failed ({}), retrying in {:?}", url, failure, delay).yellow()
+            );
+            std::thread::sleep(delay);
+            delay *= 2;
+        };
+
+        match response.status() {
+            StatusCode::OK => Ok(response),
+            StatusCode::NOT_FOUND => Err(Error::PuzzleNotFound(url.to_owned())),
+            StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                Err(Error::SessionExpired(response.status()))
             }
+            status if status.is_server_error() => Err(Error::ServerError(status)),
+            status => Err(Error::UnexpectedStatus(status)),
         }
+    }
 
-        if self.input_cache.get(url).is_some() {
-            return Ok(());
+    fn cache_input(&mut self, client: &Client, day: &Day) -> Result<String> {
+        let url = self.input_url(day);
+        self.fetch_cached(client, day, &url, |_| true)
+    }
+
+    /// Downloads a page of the day's puzzle, unless a cached copy is available
+    /// that `is_complete` accepts. An incomplete copy is still used when
+    /// fetching is disabled.
+    fn fetch_cached<F>(&mut self, client: &Client, day: &Day, url: &str, is_complete: F) -> Result<String>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let cached = self.input_cache.get(url)?;
+        match cached {
+            Some(page) if is_complete(&page) || self.options.no_fetch => return Ok(page),
+            _ if self.options.no_fetch => return Err(Error::FetchDisabled(url.to_owned())),
+            _ => {}
         }
+
+        self.wait_for_unlock(day)?;
+
         let token = self.token.as_ref().ok_or(Error::MissingSessionToken)?;
 
-        let response = client
-            .get(url)
-            .header("cookie", format!("session={}", token))
-            .send()?;
+        let permit = self.rate_limiter.acquire()?;
+        let response = self.send(url, true, || {
+            permit.touch();
+            client
+                .get(url)
+                .header("cookie", format!("session={}", token))
+                .send()
+        })?;
+        drop(permit);
+
+        let result = trim_trailing_newlines(response.text()?);
+        self.input_cache.insert(url, &result)?;
+        Ok(result)
+    }
 
-        if response.status() != StatusCode::OK {
-            return Err(Error::InvalidSessionToken(response.status()));
+    /// Fails if the puzzle is still locked, or with the `wait` option, sleeps
+    /// until it unlocks.
+    fn wait_for_unlock(&self, day: &Day) -> Result<()> {
+        while let Some(remaining) = release::time_until_unlock(&*self.clock, YEAR, day.number) {
+            if !self.options.wait {
+                return Err(Error::PuzzleLocked(day.name, remaining));
+            }
+            let message = format!(
+                "{} unlocks in {}, waiting",
+                day.name,
+                release::format_countdown(remaining)
+            );
+            eprintln!("{}", message.yellow());
+            self.clock.sleep(remaining);
         }
+        Ok(())
+    }
 
-        let mut result = response.text()?;
+    fn input_path(&self, day: &str, variant: Option<&str>) -> PathBuf {
+        match variant {
+            Some(variant) => self.input_dir.join(format!("{}.{}.txt", day, variant)),
+            None => self.input_dir.join(format!("{}.txt", day)),
+        }
+    }
 
-        // Strip trailing newline characters
-        while let Some(last) = result.pop() {
-            if last == '\r' || last == '\n' {
-                continue;
+    /// Loads the input for a day. A file in the input directory takes
+    /// precedence over the cache and the network, and a variant (such as a
+    /// colleague's input) must always exist as a local file.
+    fn load_input(&mut self, client: &Client, day: &Day, variant: Option<&str>) -> Result<String> {
+        if let Some(path) = &self.options.input_file {
+            if path.as_os_str() == "-" {
+                return self.read_stdin();
             }
-            result.push(last);
-            break;
+            return match std::fs::read_to_string(path) {
+                Ok(input) => Ok(trim_trailing_newlines(input)),
+                Err(e) if e.kind() == ErrorKind::NotFound => Err(Error::MissingInputFile(path.clone())),
+                Err(e) => Err(e.into()),
+            };
         }
 
-        self.input_cache.insert(url.to_owned(), result);
+        let path = self.input_path(day.name, variant);
+        match std::fs::read_to_string(&path) {
+            Ok(input) => return Ok(trim_trailing_newlines(input)),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                if variant.is_some() {
+                    return Err(Error::MissingInputFile(path));
+                }
+            }
+            Err(e) => return Err(e.into()),
+        }
 
-        let serialized = ::bincode::serialize(&self.input_cache)?;
-        ::std::fs::write("cache.dat", serialized)?;
+        self.cache_input(client, day)
+    }
 
-        self.no_fetch_before = Some(Instant::now() + Duration::from_secs(5));
+    /// The files a day's input may be loaded from, in order of precedence and
+    /// whether they exist or not. Empty when the input is read from stdin.
+    pub fn input_files(&self, day: &str, variant: Option<&str>) -> Result<Vec<PathBuf>> {
+        let day = self.get_day(day)?;
+        if let Some(path) = &self.options.input_file {
+            return Ok(if path.as_os_str() == "-" { Vec::new() } else { vec![path.clone()] });
+        }
+        let mut files = vec![self.input_path(day.name, variant)];
+        if variant.is_none() {
+            let url = self.input_url(&day);
+            if let Some((_, entry)) = self.input_cache.entries().find(|&(key, _)| key == url) {
+                files.push(self.input_cache.path(entry));
+            }
+        }
+        Ok(files)
+    }
 
-        Ok(())
+    /// Reads the input from stdin the first time, and returns the same input
+    /// afterwards, as stdin can only be read once.
+    fn read_stdin(&mut self) -> Result<String> {
+        if self.stdin_input.is_none() {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            self.stdin_input = Some(trim_trailing_newlines(input));
+        }
+        Ok(self.stdin_input.clone().unwrap())
     }
 
-    pub fn execute(&mut self, client: &Client, day: &str) -> Result<()> {
-        let day = self
-            .days
+    /// Lists the named input variants available for a day in the input
+    /// directory, in alphabetical order.
+    pub fn variants(&self, day: &str) -> Result<Vec<String>> {
+        let entries = match std::fs::read_dir(&self.input_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let prefix = format!("{}.", day);
+        let mut variants = Vec::new();
+        for entry in entries {
+            let file_name = entry?.file_name();
+            let file_name = match file_name.to_str() {
+                Some(file_name) => file_name,
+                None => continue,
+            };
+            let variant = file_name
+                .strip_prefix(prefix.as_str())
+                .and_then(|x| x.strip_suffix(".txt"));
+            if let Some(variant) = variant {
+                if !variant.is_empty() {
+                    variants.push(variant.to_owned());
+                }
+            }
+        }
+        variants.sort();
+        Ok(variants)
+    }
+
+    fn answer_path(&self, day: &str, variant: Option<&str>) -> PathBuf {
+        match variant {
+            Some(variant) => self.answer_dir.join(format!("{}.{}.txt", day, variant)),
+            None => self.answer_dir.join(format!("{}.txt", day)),
+        }
+    }
+
+    fn get_day(&self, day: &str) -> Result<Day> {
+        self.days
             .get(day)
-            .ok_or_else(|| Error::DayDoesNotExist(day.to_owned()))?
-            .clone();
+            .cloned()
+            .ok_or_else(|| Error::DayDoesNotExist(day.to_owned()))
+    }
+
+    /// The parts of a day to run, taking the part selected in the options into
+    /// account.
+    fn parts(&self, day: &Day) -> Vec<(usize, PartFn<Parsed>)> {
+        [day.part1, day.part2]
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, callback)| Some((idx + 1, (*callback)?)))
+            .filter(|(part, _)| self.options.part.is_none_or(|only| only == *part))
+            .collect()
+    }
+
+    /// The numbers of the parts a run of a day computes, taking the part
+    /// selected in the options into account.
+    pub fn part_numbers(&self, day: &str) -> Result<Vec<usize>> {
+        Ok(self.parts(&self.get_day(day)?).into_iter().map(|(part, _)| part).collect())
+    }
+
+    /// How long the parts of a day may run, taking the timeout selected in the
+    /// options into account.
+    fn timeout(&self, day: &Day) -> Option<Duration> {
+        match self.options.timeout {
+            Some(timeout) if timeout == Duration::ZERO => None,
+            Some(timeout) => Some(timeout),
+            None => day.timeout,
+        }
+    }
+
+    /// Runs all parts of a day, returning the part numbers and their results.
+    fn run(&mut self, client: &Client, day: &Day, variant: Option<&str>) -> Result<Vec<(usize, PartResult)>> {
+        let input = self.load_input(client, day, variant)?;
+        let parsed = Arc::new(parse(day, &input, &self.parse_options())?);
+        let mut results = Vec::new();
+        for (part, callback) in self.parts(day) {
+            let parsed = parsed.clone();
+            results.push((part, run_part(move || callback(&parsed), self.timeout(day)).0));
+        }
+        Ok(results)
+    }
 
-        self.cache_input(client, day.url)?;
-        let input = self.input_cache.get(day.url).unwrap();
-        if let Some(part1) = day.part1 {
-            println!("\n{} {}", day.name.bright_cyan().bold(), "part1");
-            println!("{}", part1(input.clone())?.bright_green());
+    /// Computes the answer to a single part of a day.
+    pub fn solve(&mut self, client: &Client, day: &str, variant: Option<&str>, part: usize) -> Result<String> {
+        let day = self.get_day(day)?;
+        let callback = match part {
+            1 => day.part1,
+            2 => day.part2,
+            _ => None,
         }
-        if let Some(part2) = day.part2 {
-            println!("{} {}", day.name.bright_cyan().bold(), "part2");
-            println!("{}", part2(input.clone())?.bright_green());
+        .ok_or_else(|| Error::PartDoesNotExist(day.name.to_owned(), part))?;
+        let input = self.load_input(client, &day, variant)?;
+        callback(&parse(&day, &input, &self.parse_options())?)
+    }
+
+    /// Downloads the puzzle page of a day, and whether it's complete. The page
+    /// only includes part 2 once part 1 is solved, so until then it's
+    /// downloaded again every time.
+    fn puzzle_page(&mut self, client: &Client, day: &Day) -> Result<(String, bool)> {
+        let url = self.puzzle_url(day);
+        let is_complete = |page: &str| day.part2.is_none() || describe::has_part2(page);
+        let page = self.fetch_cached(client, day, &url, is_complete)?;
+        let is_complete = is_complete(&page);
+        Ok((page, is_complete))
+    }
+
+    /// Renders the puzzle description of a day.
+    pub fn describe(&mut self, client: &Client, day: &str) -> Result<String> {
+        let day = self.get_day(day)?;
+        let (page, is_complete) = self.puzzle_page(client, &day)?;
+        let mut description = describe::render(&page)?;
+        if !is_complete {
+            description.push_str(&format!("\n{}\n", "Part 2 is shown once part 1 is solved.".dimmed()));
+        }
+        Ok(description)
+    }
+
+    /// Adds the code blocks in the puzzle description of a day to the example
+    /// corpus, returning the paths of the new examples.
+    pub fn extract_examples(&mut self, client: &Client, corpus: &Corpus, day: &str) -> Result<Vec<PathBuf>> {
+        let day = self.get_day(day)?;
+        let (page, _) = self.puzzle_page(client, &day)?;
+        let mut paths = Vec::new();
+        for block in examples::extract(&page) {
+            if let Some(path) = corpus.add(day.name, &block)? {
+                paths.push(path);
+            }
         }
+        Ok(paths)
+    }
+
+    /// Runs the parts of a day on its examples and compares the answers with
+    /// the expected answers, skipping parts that don't have one. Returns
+    /// whether all of them passed.
+    pub fn check_examples(&self, corpus: &Corpus, day: &str) -> Result<bool> {
+        let mut all_passed = true;
+        for example in corpus.load(day)? {
+            // The parameters of the example take precedence over the overrides
+            let mut day = self.get_day(day)?;
+            let mut overrides = param_overrides(&self.params, day.name);
+            overrides.extend(example.params.iter().map(|(name, value)| (name.as_str(), value.as_str())));
+            day.params = (day.make_params)(day.name, &overrides)?;
 
+            let parts = self
+                .parts(&day)
+                .into_iter()
+                .filter(|&(part, _)| example.expected.get(part).is_some())
+                .collect::<Vec<_>>();
+            if parts.is_empty() {
+                continue;
+            }
+            let parsed = match parse(&day, &example.input, &self.parse_options()) {
+                Ok(parsed) => Arc::new(parsed),
+                Err(e) => {
+                    println!("{} {} {}", example.name.bright_cyan().bold(), "ERROR".bright_red(), e);
+                    all_passed = false;
+                    continue;
+                }
+            };
+
+            for (part, callback) in parts {
+                let parsed = parsed.clone();
+                let label = format!("{} part{}", example.name, part);
+                let answer = match run_part(move || callback(&parsed), self.timeout(&day)).0 {
+                    PartResult::Answer(answer) => answer,
+                    PartResult::Error(e) => {
+                        println!("{} {} {}", label.bright_cyan().bold(), "ERROR".bright_red(), e);
+                        all_passed = false;
+                        continue;
+                    }
+                    PartResult::Panic(message) => {
+                        println!("{} {} {}", label.bright_cyan().bold(), "PANIC".bright_red(), message);
+                        all_passed = false;
+                        continue;
+                    }
+                    PartResult::Timeout(duration) => {
+                        println!("{} {} after {:?}", label.bright_cyan().bold(), "TIMEOUT".bright_red(), duration);
+                        all_passed = false;
+                        continue;
+                    }
+                };
+                match example.expected.verify(part, &answer) {
+                    Verdict::Pass => println!("{} {} {}", label.bright_cyan().bold(), "PASS".bright_green(), answer),
+                    Verdict::Fail(expected) => {
+                        println!(
+                            "{} {} {} (expected {})",
+                            label.bright_cyan().bold(),
+                            "FAIL".bright_red(),
+                            answer,
+                            expected
+                        );
+                        all_passed = false;
+                    }
+                    Verdict::New => {}
+                }
+            }
+        }
+        Ok(all_passed)
+    }
+
+    /// Computes the answer to a part and submits it to the server, unless the
+    /// same answer already received a verdict before. Every verdict is stored,
+    /// and a correct answer is recorded as the accepted answer.
+    pub fn submit(&mut self, client: &Client, day: &str, part: usize) -> Result<(String, Outcome)> {
+        let answer = self.solve(client, day, None, part)?;
+        let day = self.get_day(day)?;
+
+        let mut submissions = Submissions::load(&self.submissions_path)?;
+        if let Some(outcome) = submissions.find(day.name, part, &answer) {
+            return Ok((answer, outcome));
+        }
+
+        let token = self.token.as_ref().ok_or(Error::MissingSessionToken)?;
+        let url = self.answer_url(&day);
+        let response = self.send(&url, false, || {
+            client
+                .post(&url)
+                .header("cookie", format!("session={}", token))
+                .form(&[("level", part.to_string()), ("answer", answer.clone())])
+                .send()
+        })?;
+        let outcome = submit::parse_response(&response.text()?)?;
+
+        submissions.insert(day.name, part, answer.clone(), outcome);
+        submissions.save(&self.submissions_path)?;
+        if outcome == Outcome::Correct {
+            let path = self.answer_path(day.name, None);
+            let mut answers = Answers::load(&path)?;
+            answers.set(part, answer.clone());
+            answers.save(&path)?;
+        }
+
+        Ok((answer, outcome))
+    }
+
+    /// Runs every part of the given days on a pool of `jobs` threads. The
+    /// results of each day are printed as text (unless another output format
+    /// is selected) and passed to `on_done` once the day completes, in the
+    /// order the days were given. Failing parts don't prevent the other parts
+    /// from running, only a failure to load or parse the input is passed as
+    /// an error.
+    pub fn execute<F>(&mut self, client: &Client, days: &[(&str, Option<&str>)], mut on_done: F)
+    where
+        F: FnMut(&str, Option<&str>, Result<DayRun>),
+    {
+        // Inputs are loaded up front, as that may hit the network and update
+        // the cache, which the threads shouldn't do.
+        let mut results = Vec::with_capacity(days.len());
+        let mut inputs = Vec::with_capacity(days.len());
+        for &(day, variant) in days {
+            match self.get_day(day).and_then(|day| Ok((self.load_input(client, &day, variant)?, day))) {
+                Ok(input) => {
+                    results.push(None);
+                    inputs.push(Some(input));
+                }
+                Err(e) => {
+                    results.push(Some(Err(e)));
+                    inputs.push(None);
+                }
+            }
+        }
+        let mut runs: Vec<Vec<PartRun>> = days.iter().map(|_| Vec::new()).collect();
+        let mut remaining = vec![0; days.len()];
+
+        enum Task {
+            Parse(usize),
+            Part(usize, usize, PartFn<Parsed>, Arc<Parsed>, Option<Duration>),
+        }
+        enum Event {
+            Parsed(usize, Result<Parsed>),
+            Part(usize, PartRun),
+        }
+
+        let (task_tx, task_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        let task_rx = Mutex::new(task_rx);
+        let inputs = &inputs;
+        let options = &self.parse_options();
+        thread::scope(|scope| {
+            for _ in 0..self.options.jobs.max(1) {
+                let task_rx = &task_rx;
+                let event_tx = event_tx.clone();
+                scope.spawn(move || loop {
+                    let task = match task_rx.lock().unwrap().recv() {
+                        Ok(task) => task,
+                        Err(_) => break,
+                    };
+                    let event = match task {
+                        Task::Parse(idx) => {
+                            let (input, day) = inputs[idx].as_ref().unwrap();
+                            Event::Parsed(idx, parse(day, input, options))
+                        }
+                        Task::Part(idx, part, callback, parsed, timeout) => {
+                            let (result, duration) = run_part(move || callback(&parsed), timeout);
+                            Event::Part(idx, PartRun { part, result, duration })
+                        }
+                    };
+                    if event_tx.send(event).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(event_tx);
+
+            for (idx, input) in inputs.iter().enumerate() {
+                if input.is_some() {
+                    task_tx.send(Task::Parse(idx)).unwrap();
+                }
+            }
+
+            let mut next = 0;
+            loop {
+                while let Some(Some(result)) = results.get_mut(next).map(Option::take) {
+                    let (day, variant) = days[next];
+                    if let Ok(run) = &result {
+                        self.print_day(&title(day, variant), run);
+                    }
+                    on_done(day, variant, result);
+                    next += 1;
+                }
+                if next == days.len() {
+                    break;
+                }
+
+                let idx = match event_rx.recv().expect("worker threads stopped") {
+                    Event::Parsed(idx, Ok(parsed)) => {
+                        let parsed = Arc::new(parsed);
+                        let day = &inputs[idx].as_ref().unwrap().1;
+                        let parts = self.parts(day);
+                        remaining[idx] = parts.len();
+                        for (part, callback) in parts {
+                            let task = Task::Part(idx, part, callback, parsed.clone(), self.timeout(day));
+                            task_tx.send(task).unwrap();
+                        }
+                        idx
+                    }
+                    Event::Parsed(idx, Err(e)) => {
+                        results[idx] = Some(Err(e));
+                        continue;
+                    }
+                    Event::Part(idx, run) => {
+                        runs[idx].push(run);
+                        remaining[idx] -= 1;
+                        idx
+                    }
+                };
+                if remaining[idx] == 0 {
+                    let mut parts = std::mem::take(&mut runs[idx]);
+                    parts.sort_by_key(|run| run.part);
+                    let input_hash = input_hash(&inputs[idx].as_ref().unwrap().0);
+                    results[idx] = Some(Ok(DayRun { input_hash, parts }));
+                }
+            }
+            drop(task_tx);
+        });
+    }
+
+    /// Prints the results of a day as text, unless another output format is
+    /// selected.
+    fn print_day(&self, title: &str, run: &DayRun) {
+        if self.options.format != Format::Text {
+            return;
+        }
+        for (idx, part) in run.parts.iter().enumerate() {
+            if !self.options.quiet {
+                let separator = if idx == 0 { "\n" } else { "" };
+                println!("{}{} part{}", separator, title.bright_cyan().bold(), part.part);
+            }
+            match &part.result {
+                PartResult::Answer(answer) => println!("{}", answer.bright_green()),
+                PartResult::Error(e) => println!("{}", e.to_string().bright_red()),
+                PartResult::Panic(message) => println!("{} {}", "panicked:".bright_red(), message.bright_red()),
+                PartResult::Timeout(duration) => {
+                    println!("{}", format!("timed out after {:?}", duration).bright_red())
+                }
+            }
+        }
+    }
+
+    /// Parses the input and runs every part of a day `warmup` times, and then
+    /// measures `iterations` more runs. Returns a `dayXX parse` or `dayXX partN`
+    /// label and the statistics for parsing and each part.
+    pub fn bench(
+        &mut self,
+        client: &Client,
+        day: &str,
+        variant: Option<&str>,
+        warmup: usize,
+        iterations: usize,
+    ) -> Result<Vec<(String, Stats)>> {
+        let day = self.get_day(day)?;
+        let title = title(day.name, variant);
+        let input = self.load_input(client, &day, variant)?;
+        let mut results = Vec::new();
+        let options = self.parse_options();
+        for _ in 0..warmup {
+            (day.parse)(&input, &options, &day.params)?;
+        }
+        let mut samples = Vec::with_capacity(iterations);
+        for _ in 0..iterations.max(1) {
+            let start = Instant::now();
+            (day.parse)(&input, &options, &day.params)?;
+            samples.push(start.elapsed());
+        }
+        results.push((format!("{} parse", title), Stats::from_samples(&mut samples)));
+
+        let parsed = (day.parse)(&input, &options, &day.params)?;
+        for (part, callback) in self.parts(&day) {
+            for _ in 0..warmup {
+                callback(&parsed)?;
+            }
+            let mut samples = Vec::with_capacity(iterations);
+            for _ in 0..iterations.max(1) {
+                let start = Instant::now();
+                callback(&parsed)?;
+                samples.push(start.elapsed());
+            }
+            results.push((format!("{} part{}", title, part), Stats::from_samples(&mut samples)));
+        }
+        Ok(results)
+    }
+
+    /// Runs a day against the given variant, or its default input and every
+    /// local variant, and compares the results with the accepted answers. When
+    /// `accept` is set, answers for parts without an accepted answer are
+    /// recorded. Returns whether every part passed.
+    pub fn check(&mut self, client: &Client, day: &str, variant: Option<&str>, accept: bool) -> Result<bool> {
+        let day = self.get_day(day)?;
+        let variants = match variant {
+            Some(variant) => vec![Some(variant.to_owned())],
+            None => {
+                let mut variants = vec![None];
+                variants.extend(self.variants(day.name)?.into_iter().map(Some));
+                variants
+            }
+        };
+
+        let mut all_passed = true;
+        for variant in &variants {
+            let variant = variant.as_deref();
+            let title = title(day.name, variant);
+            let results = match self.run(client, &day, variant) {
+                Ok(results) => results,
+                Err(e) => {
+                    println!("{} {} {}", title.bright_cyan().bold(), "ERROR".bright_red(), e);
+                    all_passed = false;
+                    continue;
+                }
+            };
+
+            let path = self.answer_path(day.name, variant);
+            let mut answers = Answers::load(&path)?;
+            let mut is_modified = false;
+            for (part, result) in results {
+                let label = format!("{} part{}", title, part);
+                let answer = match result {
+                    PartResult::Answer(answer) => answer,
+                    PartResult::Error(e) => {
+                        println!("{} {} {}", label.bright_cyan().bold(), "ERROR".bright_red(), e);
+                        all_passed = false;
+                        continue;
+                    }
+                    PartResult::Panic(message) => {
+                        println!("{} {} {}", label.bright_cyan().bold(), "PANIC".bright_red(), message);
+                        all_passed = false;
+                        continue;
+                    }
+                    PartResult::Timeout(duration) => {
+                        println!("{} {} after {:?}", label.bright_cyan().bold(), "TIMEOUT".bright_red(), duration);
+                        all_passed = false;
+                        continue;
+                    }
+                };
+                match answers.verify(part, &answer) {
+                    Verdict::Pass => println!("{} {} {}", label.bright_cyan().bold(), "PASS".bright_green(), answer),
+                    Verdict::Fail(expected) => {
+                        println!(
+                            "{} {} {} (expected {})",
+                            label.bright_cyan().bold(),
+                            "FAIL".bright_red(),
+                            answer,
+                            expected
+                        );
+                        all_passed = false;
+                    }
+                    Verdict::New => {
+                        println!("{} {} {}", label.bright_cyan().bold(), "NEW".bright_yellow(), answer);
+                        if accept {
+                            answers.set(part, answer);
+                            is_modified = true;
+                        }
+                    }
+                }
+            }
+            if is_modified {
+                answers.save(&path)?;
+            }
+        }
+
+        Ok(all_passed)
+    }
+}
+
+thread_local! {
+    static IS_CATCHING_PANIC: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+    static CANCELLATION: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// Signals a part that it ran out of time. Parts don't receive the token
+/// directly, instead long-running parts poll `check_cancelled`.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Fails once the part running on this thread has been cancelled, so that
+/// long-running loops can stop early by polling it every so often.
+pub fn check_cancelled() -> Result<()> {
+    let is_cancelled = CANCELLATION.with(|cell| cell.borrow().as_ref().is_some_and(CancellationToken::is_cancelled));
+    if is_cancelled {
+        Err(Error::Cancelled)
+    } else {
         Ok(())
     }
 }
+
+/// Runs a single part, catching both errors and panics. With a timeout, the
+/// part runs on its own thread, and is reported as timed out and cancelled
+/// once it takes longer. A part that doesn't poll for cancellation keeps
+/// running in the background until it completes.
+fn run_part<F>(callback: F, timeout: Option<Duration>) -> (PartResult, Duration)
+where
+    F: FnOnce() -> Result<String> + Send + 'static,
+{
+    fn run<F: FnOnce() -> Result<String>>(callback: F) -> (PartResult, Duration) {
+        let start = Instant::now();
+        let result = catch_panic(callback);
+        let duration = start.elapsed();
+        let result = match result {
+            Ok(Ok(answer)) => PartResult::Answer(answer),
+            Ok(Err(e)) => PartResult::Error(e),
+            Err(message) => PartResult::Panic(message),
+        };
+        (result, duration)
+    }
+
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return run(callback),
+    };
+    let token = CancellationToken::default();
+    let (tx, rx) = mpsc::channel();
+    let part_token = token.clone();
+    thread::spawn(move || {
+        CANCELLATION.with(|cell| *cell.borrow_mut() = Some(part_token));
+        let _ = tx.send(run(callback));
+    });
+    match rx.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(_) => {
+            token.cancel();
+            (PartResult::Timeout(timeout), timeout)
+        }
+    }
+}
+
+/// Parses the input of a day, turning a panic into an error since none of
+/// the parts can run without the parsed input.
+fn parse(day: &Day, input: &str, options: &ParseOptions) -> Result<Parsed> {
+    catch_panic(|| (day.parse)(input, options, &day.params)).map_err(Error::ParsePanic)?
+}
+
+/// Parses every non-empty line of the input with `parse_line`, which is given
+/// the (zero-based) index of the line. A line that fails to parse fails the
+/// whole input, with the error of the first such line and the numbers of the
+/// others, unless parsing is lenient, in which case they are skipped.
+pub fn try_parse_lines<'a, T, F>(input: &'a str, options: &ParseOptions, mut parse_line: F) -> Result<Vec<T>>
+where
+    F: FnMut(usize, &'a str) -> Result<T>,
+{
+    let mut parsed = Vec::new();
+    let mut error = None;
+    let mut other_lines = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_line(idx, line) {
+            Ok(value) => parsed.push(value),
+            Err(e) if error.is_none() => error = Some(e),
+            Err(_) => other_lines.push(idx),
+        }
+    }
+    match error {
+        Some(e) if !options.lenient => Err(e.also_at(&other_lines)),
+        _ => Ok(parsed),
+    }
+}
+
+/// Like `try_parse_lines`, for parsers that don't explain what's wrong with a
+/// line: every line they return `None` for fails with `expected`.
+pub fn parse_lines<'a, T, F>(
+    input: &'a str,
+    options: &ParseOptions,
+    expected: &'static str,
+    mut parse_line: F,
+) -> Result<Vec<T>>
+where
+    F: FnMut(&'a str) -> Option<T>,
+{
+    try_parse_lines(input, options, |idx, line| {
+        parse_line(line).ok_or_else(|| Error::input(expected).at(idx, line, line))
+    })
+}
+
+/// The `(name, value)` overrides of a day's parameters.
+fn param_overrides<'a>(params: &'a [(String, String, String)], day: &str) -> Vec<(&'a str, &'a str)> {
+    params
+        .iter()
+        .filter(|(d, _, _)| d == day)
+        .map(|(_, name, value)| (name.as_str(), value.as_str()))
+        .collect()
+}
+
+/// Calls `f`, catching a panic and returning its message along with the
+/// location it occurred at. The panic hook is replaced so that such panics
+/// are reported with the part, instead of being printed to stderr.
+fn catch_panic<T, F: FnOnce() -> T>(f: F) -> std::result::Result<T, String> {
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CATCHING_PANIC.with(Cell::get) {
+                let location = info.location().map(|location| location.to_string());
+                PANIC_LOCATION.with(|cell| *cell.borrow_mut() = location);
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    IS_CATCHING_PANIC.with(|cell| cell.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    IS_CATCHING_PANIC.with(|cell| cell.set(false));
+
+    result.map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_owned());
+        match PANIC_LOCATION.with(|cell| cell.borrow_mut().take()) {
+            Some(location) => format!("{} ({})", message, location),
+            None => message,
+        }
+    })
+}
+
+/// Identifies an input by the hex encoded SHA-256 of its contents.
+pub fn input_hash(input: &str) -> String {
+    use crypto::{digest::Digest, sha2::Sha256};
+    let mut hasher = Sha256::new();
+    hasher.input_str(input);
+    hasher.result_str()
+}
+
+/// The name of a day and input variant in messages and output records, in
+/// the same `day.variant` form that selects them on the command line.
+pub fn title(day: &str, variant: Option<&str>) -> String {
+    match variant {
+        Some(variant) => format!("{}.{}", day, variant),
+        None => day.to_owned(),
+    }
+}
+
+/// Strips trailing newline characters, so that inputs from the network and
+/// from local files (which editors tend to end with a newline) are identical.
+pub fn trim_trailing_newlines(mut input: String) -> String {
+    while let Some(last) = input.pop() {
+        if last == '\r' || last == '\n' {
+            continue;
+        }
+        input.push(last);
+        break;
+    }
+    input
+}
+
+/// A day whose first part answers with its input, for testing the framework.
+#[cfg(test)]
+struct Echo;
+
+#[cfg(test)]
+impl Solution for Echo {
+    type Parsed = String;
+    type Params = ();
+    const HAS_PART2: bool = false;
+    fn parse(input: &str, _: &ParseOptions) -> Result<String> {
+        Ok(input.to_owned())
+    }
+    fn part1(input: &String, _: &()) -> Result<String> {
+        Ok(input.clone())
+    }
+    fn part2(_: &String, _: &()) -> Result<String> {
+        unreachable!()
+    }
+}
+
+#[test]
+fn fetch_input_test() {
+    use crate::mock_server::{temp_dir, test_framework, MockServer};
+    let server = MockServer::start(vec![(200, "((())\n")]);
+    let dir = temp_dir("fetch_input");
+    let mut fw = test_framework(dir.clone(), &server.url);
+    fw.register_day::<Echo>("day01", 1);
+
+    let day = fw.get_day("day01").unwrap();
+    let input = fw.load_input(&Client::new(), &day, None).unwrap();
+    assert_eq!(input, "((())");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/2015/day/1/input");
+    assert_eq!(requests[0].header("cookie"), Some("session=abc"));
+    assert!(dir.join("cache/manifest.json").exists());
+
+    // Served from the cache the second time around
+    fw.load_input(&Client::new(), &day, None).unwrap();
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn input_file_test() {
+    use crate::mock_server::{temp_dir, test_framework};
+    let dir = temp_dir("input_file");
+    let mut fw = test_framework(dir.clone(), "");
+    fw.register_day::<Echo>("day01", 1);
+    let day = fw.get_day("day01").unwrap();
+
+    // Neither the cache nor the network is involved
+    std::fs::write(dir.join("edited.txt"), "(()\r\n\r\n").unwrap();
+    fw.options.input_file = Some(dir.join("edited.txt"));
+    assert_eq!(fw.load_input(&Client::new(), &day, None).unwrap(), "(()");
+    fw.options.input_file = Some(dir.join("missing.txt"));
+    assert!(matches!(
+        fw.load_input(&Client::new(), &day, None),
+        Err(Error::MissingInputFile(_))
+    ));
+
+    // Stdin is only read once
+    fw.stdin_input = Some("())".to_owned());
+    fw.options.input_file = Some(PathBuf::from("-"));
+    assert_eq!(fw.load_input(&Client::new(), &day, None).unwrap(), "())");
+}
+
+#[test]
+fn params_test() {
+    use crate::mock_server::{temp_dir, test_framework};
+    let dir = temp_dir("params");
+    let mut fw = test_framework(dir.clone(), "");
+    crate::day17::register_day(&mut fw);
+    std::fs::write(dir.join("sizes.txt"), "20\n15\n10\n5\n5").unwrap();
+    fw.options.input_file = Some(dir.join("sizes.txt"));
+
+    let param = |name: &str, value: &str| vec![("day17".to_owned(), name.to_owned(), value.to_owned())];
+    fw.set_params(&param("liters", "25")).unwrap();
+    assert_eq!(fw.solve(&Client::new(), "day17", None, 1).unwrap(), "4");
+    assert_eq!(fw.solve(&Client::new(), "day17", None, 2).unwrap(), "3");
+    assert!(matches!(fw.set_params(&param("litres", "25")), Err(Error::UnknownParam(..))));
+    assert!(matches!(fw.set_params(&param("liters", "-1")), Err(Error::InvalidParam(..))));
+    let unknown_day = vec![("day99".to_owned(), "x".to_owned(), "1".to_owned())];
+    assert!(matches!(fw.set_params(&unknown_day), Err(Error::DayDoesNotExist(_))));
+}
+
+#[test]
+fn parse_lines_test() {
+    let parse = |input, lenient| {
+        let options = ParseOptions { lenient };
+        parse_lines(input, &options, "expected a number", |line| line.parse::<u32>().ok())
+    };
+    assert_eq!(parse("1\n\n2\r\n3", false).unwrap(), [1, 2, 3]);
+    let e = parse("1\nx\n2\ny\nz", false).unwrap_err().with_day("day01");
+    assert_eq!(
+        e.to_string(),
+        "day01: expected a number at line 2, column 1 (also lines 4, 5)\n    2 | x\n      | ^"
+    );
+    assert_eq!(parse("1\nx\n2\ny\nz", true).unwrap(), [1, 2]);
+}
+
+#[test]
+fn locked_puzzle_test() {
+    use crate::mock_server::{temp_dir, test_framework, MockServer};
+    use crate::release::{unlock_time, MockClock};
+    let server = MockServer::start(vec![(200, "()")]);
+    let mut fw = test_framework(temp_dir("locked_puzzle"), &server.url);
+    fw.register_day::<Echo>("day02", 2);
+    let clock = Arc::new(MockClock::new(unlock_time(YEAR, 2) - Duration::from_secs(3600)));
+    fw.clock = clock.clone();
+    let day = fw.get_day("day02").unwrap();
+
+    match fw.load_input(&Client::new(), &day, None) {
+        Err(Error::PuzzleLocked("day02", remaining)) => assert_eq!(remaining, Duration::from_secs(3600)),
+        result => panic!("expected the puzzle to be locked, got {:?}", result.map(|_| ())),
+    }
+    assert!(server.requests().is_empty());
+
+    fw.options.wait = true;
+    assert_eq!(fw.load_input(&Client::new(), &day, None).unwrap(), "()");
+    assert_eq!(clock.now(), unlock_time(YEAR, 2));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn describe_test() {
+    use crate::mock_server::{temp_dir, test_framework, MockServer};
+    colored::control::set_override(false);
+    let server = MockServer::start(vec![
+        (200, "<main><article><h2>--- Day 1: Echo ---</h2><p>Part one.</p></article></main>"),
+        (200, "<main><article><p>Part one.</p></article><article><p>Part <em>two</em>.</p></article></main>"),
+    ]);
+    let mut fw = test_framework(temp_dir("describe"), &server.url);
+    fw.register_day::<Echo>("day01", 1);
+    // Pretend the day has a second part, so that the page is incomplete
+    // without it
+    fw.days.get_mut("day01").unwrap().part2 = Some(part1_erased::<Echo>);
+    let client = Client::new();
+
+    assert_eq!(
+        fw.describe(&client, "day01").unwrap(),
+        "--- Day 1: Echo ---\n\nPart one.\n\nPart 2 is shown once part 1 is solved.\n"
+    );
+    assert_eq!(server.requests()[0].path, "/2015/day/1");
+    assert_eq!(fw.describe(&client, "day01").unwrap(), "Part one.\n\nPart two.\n");
+    // Complete now, so served from the cache
+    fw.describe(&client, "day01").unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn execute_test() {
+    use crate::mock_server::{temp_dir, test_framework};
+    let dir = temp_dir("execute");
+    std::fs::create_dir(dir.join("inputs")).unwrap();
+    let mut fw = test_framework(dir.clone(), "");
+    fw.set_options(Options {
+        no_fetch: true,
+        format: Format::Json,
+        jobs: 4,
+        ..Options::default()
+    });
+    let names = ["day01", "day02", "day03", "day04", "day05", "day06"];
+    for (idx, name) in names.iter().enumerate() {
+        fw.register_day::<Echo>(name, idx as u32 + 1);
+        if *name != "day04" {
+            std::fs::write(dir.join(format!("inputs/{}.txt", name)), name).unwrap();
+        }
+    }
+
+    let days = names.iter().map(|&name| (name, None)).collect::<Vec<_>>();
+    let mut results = Vec::new();
+    fw.execute(&Client::new(), &days, |day, _, result| {
+        let answer = result.map(|run| match &run.parts[..] {
+            [PartRun { result: PartResult::Answer(answer), .. }] => answer.clone(),
+            parts => panic!("unexpected parts {:?}", parts),
+        });
+        results.push((day.to_owned(), answer.ok()));
+    });
+    // Reported in order, with the day without an input as a failure
+    assert_eq!(
+        results,
+        names
+            .iter()
+            .map(|&name| (name.to_owned(), (name != "day04").then(|| name.to_owned())))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn submit_test() {
+    use crate::mock_server::{temp_dir, test_framework, MockServer};
+    let server = MockServer::start(vec![
+        (200, "<article><p>That's not the right answer; your answer is too low.</p></article>"),
+        (200, "<article><p>That's the right answer!</p></article>"),
+    ]);
+    let dir = temp_dir("submit");
+    std::fs::create_dir(dir.join("inputs")).unwrap();
+    let mut fw = test_framework(dir.clone(), &server.url);
+    fw.register_day::<Echo>("day07", 7);
+
+    let client = Client::new();
+    std::fs::write(dir.join("inputs/day07.txt"), "41\n").unwrap();
+    assert_eq!(fw.submit(&client, "day07", 1).unwrap(), ("41".to_owned(), Outcome::TooLow));
+    // The same answer isn't submitted twice
+    assert_eq!(fw.submit(&client, "day07", 1).unwrap(), ("41".to_owned(), Outcome::TooLow));
+    std::fs::write(dir.join("inputs/day07.txt"), "42\n").unwrap();
+    assert_eq!(fw.submit(&client, "day07", 1).unwrap(), ("42".to_owned(), Outcome::Correct));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2015/day/7/answer");
+    assert_eq!(requests[0].header("cookie"), Some("session=abc"));
+    assert_eq!(requests[0].body, "level=1&answer=41");
+    assert_eq!(requests[1].body, "level=1&answer=42");
+
+    let answers = Answers::load(&dir.join("answers/day07.txt")).unwrap();
+    assert_eq!(answers.get(1), Some("42"));
+}
+
+#[test]
+fn network_errors_test() {
+    use crate::mock_server::{temp_dir, test_framework, MockServer};
+    fn framework(name: &str, server: &MockServer) -> Framework {
+        let mut fw = test_framework(temp_dir(name), &server.url);
+        fw.retry_delay = Duration::from_millis(1);
+        fw.register_day::<Echo>("day01", 1);
+        fw
+    }
+    let client = Client::new();
+
+    // Transient server errors are retried
+    let server = MockServer::start(vec![(500, ""), (503, ""), (200, "(()")]);
+    let mut fw = framework("retry", &server);
+    assert_eq!(fw.solve(&client, "day01", None, 1).unwrap(), "(()");
+    assert_eq!(server.requests().len(), 3);
+
+    // Until the retries run out
+    let server = MockServer::start(vec![(502, ""); 4]);
+    let mut fw = framework("retry_exhausted", &server);
+    match fw.solve(&client, "day01", None, 1) {
+        Err(Error::ServerError(StatusCode::BAD_GATEWAY)) => {}
+        result => panic!("unexpected result {:?}", result),
+    }
+    assert_eq!(server.requests().len(), 4);
+
+    let server = MockServer::start(vec![(404, "Please don't repeatedly request this endpoint before it unlocks!")]);
+    let mut fw = framework("not_found", &server);
+    match fw.solve(&client, "day01", None, 1) {
+        Err(Error::PuzzleNotFound(url)) => assert_eq!(url, format!("{}/2015/day/1/input", server.url)),
+        result => panic!("unexpected result {:?}", result),
+    }
+
+    let server = MockServer::start(vec![(400, "Please log in to get your puzzle input.")]);
+    let mut fw = framework("expired", &server);
+    match fw.solve(&client, "day01", None, 1) {
+        Err(Error::SessionExpired(StatusCode::BAD_REQUEST)) => {}
+        result => panic!("unexpected result {:?}", result),
+    }
+    assert_eq!(server.requests().len(), 1);
+
+    // Submissions aren't retried, the server may have received them
+    let server = MockServer::start(vec![(200, "(()"), (500, "")]);
+    let mut fw = framework("submit_error", &server);
+    match fw.submit(&client, "day01", 1) {
+        Err(Error::ServerError(StatusCode::INTERNAL_SERVER_ERROR)) => {}
+        result => panic!("unexpected result {:?}", result),
+    }
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn run_part_test() {
+    match run_part(|| Ok("42".to_owned()), None).0 {
+        PartResult::Answer(answer) => assert_eq!(answer, "42"),
+        result => panic!("unexpected result {:?}", result),
+    }
+    assert_eq!(run_part(|| Err(Error::input("bad input")), None).0.status(), Status::Err);
+    match run_part(|| panic!("invalid character escape sequence"), None).0 {
+        PartResult::Panic(message) => {
+            assert!(message.starts_with("invalid character escape sequence (src/framework.rs:"))
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+
+    let timeout = Some(Duration::from_millis(50));
+    assert_eq!(run_part(|| Ok(String::new()), timeout).0.status(), Status::Ok);
+    let (sender, receiver) = mpsc::channel();
+    let poll = move || loop {
+        if let Err(e) = check_cancelled() {
+            sender.send(()).unwrap();
+            return Err(e);
+        }
+        thread::sleep(Duration::from_millis(1));
+    };
+    assert_eq!(run_part(poll, timeout).0.status(), Status::Timeout);
+    // The part stops once it notices that it was cancelled
+    receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let day = Day {
+        name: "day08",
+        number: 8,
+        parse: |_, _, _| panic!("unterminated string"),
+        part1: None,
+        part2: None,
+        timeout: None,
+        params: Arc::new(()),
+        make_params: make_params_erased::<Echo>,
+    };
+    match parse(&day, "", &ParseOptions::default()) {
+        Err(Error::ParsePanic(message)) => assert!(message.starts_with("unterminated string (")),
+        Err(e) => panic!("unexpected error {}", e),
+        Ok(_) => panic!("parsing should have failed"),
+    }
+}
@@ -0,0 +1,126 @@
+//! Random puzzle inputs, for fuzzing the parsers and for benchmarking the
+//! solutions on inputs of other sizes than the real one. Every day implements
+//! [`InputGenerator`] for its `Solver`, and the same seed and size always
+//! produce the same input.
+
+use std::ops::Range;
+
+/// A small deterministic random number generator (SplitMix64), so that the
+/// generated inputs don't depend on the platform or on any other crate.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, `n` has to be positive.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0);
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in the range, which can't be empty.
+    pub fn range(&mut self, range: Range<usize>) -> usize {
+        range.start + self.below(range.end - range.start)
+    }
+
+    /// True with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// Generates `count` lines and joins them, without a trailing newline.
+    pub fn lines<F: FnMut(&mut Rng) -> String>(&mut self, count: usize, mut f: F) -> String {
+        (0..count).map(|_| f(self)).collect::<Vec<_>>().join("\n")
+    }
+
+    pub fn lowercase(&mut self) -> char {
+        (b'a' + self.below(26) as u8) as char
+    }
+
+    /// A capitalized name that hasn't been used yet, such as the names of the
+    /// cities of day 9 or the people of day 13. Names get longer as more of
+    /// them are used, so that there are always new ones left.
+    pub fn name(&mut self, used: &mut Vec<String>) -> String {
+        loop {
+            let mut name = String::new();
+            for _ in 0..self.range(2..4) + used.len() / 100 {
+                name.push_str(self.pick(&["ka", "lo", "mi", "ne", "ru", "sa", "ti", "vo", "be", "da"]));
+            }
+            name[..1].make_ascii_uppercase();
+            if !used.contains(&name) {
+                used.push(name.clone());
+                return name;
+            }
+        }
+    }
+}
+
+/// Generates syntactically valid inputs for a day. What the size means
+/// depends on the day, e.g. the number of instructions or the width of a
+/// grid, but a larger size always means a larger input.
+pub trait InputGenerator {
+    /// The size of the real puzzle inputs, or a smaller one where the random
+    /// inputs are much harder than the real ones.
+    const DEFAULT_SIZE: usize;
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+/// The generator of a day, without its type.
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    pub default_size: usize,
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    pub fn of<G: InputGenerator>() -> Generator {
+        Generator {
+            default_size: G::DEFAULT_SIZE,
+            generate: G::generate,
+        }
+    }
+
+    /// Generates an input, with the default size if none is given.
+    pub fn run(&self, seed: u64, size: Option<usize>) -> String {
+        (self.generate)(&mut Rng::new(seed), size.unwrap_or(self.default_size))
+    }
+}
+
+#[test]
+fn rng_test() {
+    let mut rng = Rng::new(7);
+    let numbers = (0..100).map(|_| rng.range(5..10)).collect::<Vec<_>>();
+    assert!(numbers.iter().all(|n| (5..10).contains(n)));
+    assert!((5..10).all(|n| numbers.contains(&n)));
+    assert_eq!(Rng::new(7).next_u64(), Rng::new(7).next_u64());
+    assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+
+    let mut items = (0..20).collect::<Vec<_>>();
+    rng.shuffle(&mut items);
+    assert_ne!(items, (0..20).collect::<Vec<_>>());
+    items.sort_unstable();
+    assert_eq!(items, (0..20).collect::<Vec<_>>());
+}
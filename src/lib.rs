@@ -0,0 +1,142 @@
+//! Solutions for the Advent of Code 2015, and the framework that downloads the
+//! inputs, runs the solutions and checks and submits the answers.
+//!
+//! Each day is a module with its parser, the types it parses into and its core
+//! algorithms, and a `Solver` that implements [`Solution`]. [`register_days`]
+//! registers all of them with a [`Framework`].
+
+#![allow(unused_imports)]
+#![feature(trait_alias, stmt_expr_attributes)]
+
+#[macro_use]
+extern crate lazy_static;
+#[macro_use]
+extern crate serde_derive;
+
+pub mod answers;
+pub mod astar;
+pub mod bench;
+pub mod cache;
+pub mod config;
+mod describe;
+pub mod error;
+pub mod examples;
+#[macro_use]
+pub mod framework;
+pub mod generate;
+#[cfg(test)]
+mod mock_server;
+pub mod output;
+mod rate_limit;
+mod release;
+pub mod submit;
+pub mod watch;
+
+pub use crate::error::{Error, Result};
+pub use crate::framework::{Framework, ParseOptions, Solution};
+
+macro_rules! days {
+    ($($days:ident),+$(,)*) => {
+        $(
+            pub mod $days;
+        )+
+
+        /// The names of all days, in order.
+        pub const DAYS: &[&str] = &[$(stringify!($days)),+];
+
+        /// Registers every day with the framework.
+        pub fn register_days(fw: &mut Framework) {
+            $(
+                $days::register_day(fw);
+            )+
+        }
+
+        /// The input generator of a day.
+        pub fn generator(day: &str) -> Option<generate::Generator> {
+            $(
+                if day == stringify!($days) {
+                    return Some(generate::Generator::of::<$days::Solver>());
+                }
+            )+
+            None
+        }
+
+        /// Checks that every day parses the inputs of its generator, and solves
+        /// them at the default size. Smaller inputs can be too small to have
+        /// an answer, e.g. a boss with a single hit point.
+        #[test]
+        fn generators_test() {
+            for day in DAYS {
+                let generator = generator(day).unwrap();
+                for seed in 0..3 {
+                    for size in [1, 5, generator.default_size] {
+                        let input = generator.run(seed, Some(size));
+                        assert_eq!(input, generator.run(seed, Some(size)));
+                        // Day 4 mines hashes, which takes seconds for any key
+                        let solve = size == generator.default_size && *day != "day04";
+                        solve_generated(day, &input, solve);
+                    }
+                }
+            }
+            fn solve_generated(day: &str, input: &str, solve: bool) {
+                $(
+                    if day == stringify!($days) {
+                        type S = $days::Solver;
+                        let params = <S as Solution>::Params::default();
+                        let result = <S as Solution>::parse(input, &ParseOptions::default()).and_then(|parsed| {
+                            if solve {
+                                <S as Solution>::part1(&parsed, &params)?;
+                            }
+                            if solve && <S as Solution>::HAS_PART2 {
+                                <S as Solution>::part2(&parsed, &params)?;
+                            }
+                            Ok(())
+                        });
+                        if let Err(e) = result {
+                            panic!("{}: {}\n{}", day, e, input);
+                        }
+                    }
+                )+
+            }
+        }
+
+        /// Checks every day against the example corpus in `examples`.
+        #[test]
+        fn examples_test() {
+            let mut fw = mock_server::test_framework(mock_server::temp_dir("examples"), "");
+            register_days(&mut fw);
+            let corpus = examples::Corpus::new(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("examples"));
+            for day in DAYS {
+                assert!(fw.check_examples(&corpus, day).unwrap());
+            }
+        }
+    };
+}
+
+#[rustfmt::skip] days!(
+    day01,
+    day02,
+    day03,
+    day04,
+    day05,
+    day06,
+    day07,
+    day08,
+    day09,
+    day10,
+    day11,
+    day12,
+    day13,
+    day14,
+    day15,
+    day16,
+    day17,
+    day18,
+    day19,
+    day20,
+    day21,
+    day22,
+    day23,
+    day24,
+    day25,
+);
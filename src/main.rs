@@ -1,97 +1,51 @@
-#![allow(unused_imports)]
-#![feature(trait_alias, stmt_expr_attributes)]
-
-#[macro_use]
-extern crate lazy_static;
-#[macro_use]
-extern crate serde_derive;
-
-mod astar;
-mod error;
-#[macro_use]
-mod framework;
+mod cli;
 
+use advent_of_code_2015::config::{Config, Environment};
+use advent_of_code_2015::{Framework, Result, DAYS};
 use colored::Colorize;
-use crate::framework::Framework;
+use crate::cli::Args;
 use reqwest::blocking::Client;
 use std::env;
 
-pub(crate) use crate::error::Error;
-pub(crate) use crate::error::Result;
-
-macro_rules! main {
-    ($($days:ident),+$(,)*) => {
-        $(
-            mod $days;
-        )+
-        fn main() {
-            if cfg!(windows) {
-                let _ = colored::control::set_virtual_terminal(true);
-            }
-
-            let mut fw = Framework::new();
-
-            $(
-                {
-                    crate::$days::register_day(&mut fw);
-                }
-            )+;
-
-            let client = Client::new();
+fn main() {
+    if cfg!(windows) {
+        let _ = colored::control::set_virtual_terminal(true);
+    }
+
+    let args = match Args::parse(env::args().skip(1).collect(), DAYS) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e.bright_red());
+            eprintln!("run with --help for usage");
+            std::process::exit(-1);
+        }
+    };
+    let env = Environment::current();
+    let config = match Config::load(&args.overrides, &env) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e.to_string().bright_red());
+            std::process::exit(-1);
+        }
+    };
 
-            let args: Vec<String> = env::args().collect();
-            match args.len() {
-                1 => {
-                    // execute all
-                    $(
-                        {
-                            if let Err(e) = fw.execute(&client, stringify!($days)) {
-                                eprintln!("{}", e.to_string().red());
-                                std::process::exit(-2);
-                            }
-                        }
-                    )+;
-                },
-                2 => {
-                    // execute specific day
-                    if let Err(e) = fw.execute(&client, args[1].as_str()) {
-                        eprintln!("{}", e.to_string().bright_red());
-                        std::process::exit(-2);
-                    }
-                },
-                _ => {
-                    eprintln!("too many arguments");
-                    std::process::exit(-1);
-                }
-            }
+    let client = Client::new();
+    if args.all_profiles {
+        std::process::exit(cli::check_profiles(&config, &env, &client, &args, framework));
+    }
+    let mut fw = match framework(&config) {
+        Ok(fw) => fw,
+        Err(e) => {
+            eprintln!("{}", e.to_string().bright_red());
+            std::process::exit(-1);
         }
     };
+    std::process::exit(cli::execute(&mut fw, &client, &args));
 }
 
-#[rustfmt::skip] main!(
-    day01,
-    day02,
-    day03,
-    day04,
-    day05,
-    day06,
-    day07,
-    day08,
-    day09,
-    day10,
-    day11,
-    day12,
-    day13,
-    day14,
-    day15,
-    day16,
-    day17,
-    day18,
-    day19,
-    day20,
-    day21,
-    day22,
-    day23,
-    day24,
-    day25,
-);
+/// Creates a framework with every day registered.
+fn framework(config: &Config) -> Result<Framework> {
+    let mut fw = Framework::new(config)?;
+    advent_of_code_2015::register_days(&mut fw);
+    Ok(fw)
+}
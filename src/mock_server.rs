@@ -0,0 +1,128 @@
+//! A minimal stand-in for the puzzle server, used by tests to exercise the
+//! network code without reaching out to adventofcode.com.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::framework::Framework;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    /// Starts a server that answers each incoming connection with the next
+    /// status code and body, and stops once all responses have been sent.
+    pub fn start(responses: Vec<(u16, &'static str)>) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = match listener.accept() {
+                    Ok(connection) => connection,
+                    Err(_) => return,
+                };
+                let mut reader = BufReader::new(stream);
+                let request = match read_request(&mut reader) {
+                    Some(request) => request,
+                    None => return,
+                };
+                recorded.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = reader.get_mut().write_all(response.as_bytes());
+            }
+        });
+        MockServer { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> Option<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_owned();
+    let path = parts.next()?.to_owned();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line).ok()?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let idx = header.find(':')?;
+        headers.push((header[..idx].to_owned(), header[idx + 1..].trim().to_owned()));
+    }
+
+    let length = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8(body).ok()?,
+    })
+}
+
+/// Creates an empty directory for a test to store its files in.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc2015-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Creates a framework that stores its files in `dir` and talks to the server
+/// at `base_url`, with a dummy session token and no delay between fetches.
+pub fn test_framework(dir: PathBuf, base_url: &str) -> Framework {
+    Framework::new(&Config {
+        dir,
+        profile: None,
+        profiles: Vec::new(),
+        base_url: base_url.to_owned(),
+        token: Some("abc".to_owned()),
+        fetch_interval: Duration::ZERO,
+    })
+    .unwrap()
+}
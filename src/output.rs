@@ -0,0 +1,175 @@
+use crate::Result;
+use std::io::Write;
+use std::str::FromStr;
+
+/// How results of the `run` command are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Colored text meant for humans, printed as the days run.
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!("unknown format {}, expected text, json, csv or tsv", s)),
+        }
+    }
+}
+
+/// Whether a part produced an answer, and if not, why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Status {
+    Ok,
+    /// The part returned an error, or the input couldn't be loaded or parsed.
+    Err,
+    Panic,
+    Timeout,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::Err => "ERR",
+            Status::Panic => "PANIC",
+            Status::Timeout => "TIMEOUT",
+        }
+    }
+}
+
+/// The machine-readable result of a single part.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    /// The day, including the input variant (e.g. `day05.alice`).
+    pub name: String,
+    pub part: usize,
+    pub status: Status,
+    pub answer: Option<String>,
+    /// Duration in seconds.
+    pub duration: Option<f64>,
+    pub error: Option<String>,
+    /// SHA-256 of the input, absent when the input couldn't be loaded.
+    pub input_hash: Option<String>,
+}
+
+const COLUMNS: [&str; 7] = ["name", "part", "status", "answer", "duration", "error", "input_hash"];
+
+impl Record {
+    fn fields(&self) -> [String; 7] {
+        [
+            self.name.clone(),
+            self.part.to_string(),
+            self.status.as_str().to_owned(),
+            self.answer.clone().unwrap_or_default(),
+            self.duration.map(|d| d.to_string()).unwrap_or_default(),
+            self.error.clone().unwrap_or_default(),
+            self.input_hash.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+pub fn write_records<W: Write>(format: Format, records: &[Record], mut out: W) -> Result<()> {
+    match format {
+        Format::Text => {}
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut out, records)?;
+            writeln!(out)?;
+        }
+        Format::Csv => {
+            writeln!(out, "{}", COLUMNS.join(","))?;
+            for record in records {
+                let fields = record.fields();
+                let fields = fields.iter().map(|field| escape_csv(field)).collect::<Vec<_>>();
+                writeln!(out, "{}", fields.join(","))?;
+            }
+        }
+        Format::Tsv => {
+            writeln!(out, "{}", COLUMNS.join("\t"))?;
+            for record in records {
+                let fields = record.fields();
+                let fields = fields.iter().map(|field| escape_tsv(field)).collect::<Vec<_>>();
+                writeln!(out, "{}", fields.join("\t"))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn escape_tsv(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[test]
+fn write_records_test() {
+    let records = [
+        Record {
+            name: "day01".to_owned(),
+            part: 1,
+            status: Status::Ok,
+            answer: Some("232".to_owned()),
+            duration: Some(0.5),
+            error: None,
+            input_hash: Some("abc".to_owned()),
+        },
+        Record {
+            name: "day23".to_owned(),
+            part: 2,
+            status: Status::Err,
+            answer: None,
+            duration: None,
+            error: Some("day23: invalid \"register\"\n    1 | inc c".to_owned()),
+            input_hash: None,
+        },
+    ];
+
+    let mut csv = Vec::new();
+    write_records(Format::Csv, &records, &mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "name,part,status,answer,duration,error,input_hash
+day01,1,OK,232,0.5,,abc
+day23,2,ERR,,,\"day23: invalid \"\"register\"\"\n    1 | inc c\",
+"
+    );
+
+    let mut tsv = Vec::new();
+    write_records(Format::Tsv, &records, &mut tsv).unwrap();
+    assert_eq!(
+        String::from_utf8(tsv).unwrap(),
+        "name\tpart\tstatus\tanswer\tduration\terror\tinput_hash
+day01\t1\tOK\t232\t0.5\t\tabc
+day23\t2\tERR\t\t\tday23: invalid \"register\"\\n    1 | inc c\t
+"
+    );
+
+    let mut json = Vec::new();
+    write_records(Format::Json, &records[..1], &mut json).unwrap();
+    let value: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(value[0]["answer"], "232");
+    assert_eq!(value[0]["status"], "OK");
+    assert_eq!(value[0]["error"], serde_json::Value::Null);
+}
@@ -0,0 +1,207 @@
+use crate::Result;
+use colored::*;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long a process holds the lock besides waiting for the interval. The
+/// holder touches the lock before every attempt to send its request, so a
+/// lock file that wasn't touched for longer than the interval plus this was
+/// left behind by a process that died while holding it.
+const LOCK_MARGIN: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Keeps requests to the server at least `interval` apart, also across
+/// processes. The time of the last request is stored in a file, and a lock
+/// file makes sure only one process at a time waits for its turn and sends a
+/// request.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    path: PathBuf,
+    lock_path: PathBuf,
+    interval: Duration,
+}
+
+/// Permission to send a request. Dropping it records the time of the request
+/// and releases the lock.
+pub struct Permit<'a> {
+    limiter: &'a RateLimiter,
+    /// Written into the lock file, so that a lock that was taken over from
+    /// this permit is never removed by it.
+    owner: String,
+}
+
+impl RateLimiter {
+    pub fn new(path: PathBuf, interval: Duration) -> RateLimiter {
+        let mut lock_path = path.clone().into_os_string();
+        lock_path.push(".lock");
+        RateLimiter {
+            path,
+            lock_path: PathBuf::from(lock_path),
+            interval,
+        }
+    }
+
+    /// Waits until no other process is sending a request and the interval
+    /// since the last request has passed.
+    pub fn acquire(&self) -> Result<Permit<'_>> {
+        let owner = unique_owner();
+        let mut is_waiting = false;
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&self.lock_path) {
+                Ok(mut file) => {
+                    file.write_all(owner.as_bytes())?;
+                    break;
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if let Some(stale_owner) = self.stale_lock_owner() {
+                        self.remove_lock(&stale_owner);
+                        continue;
+                    }
+                    if !is_waiting {
+                        let message = format!(
+                            "waiting for another process to finish its request ({})",
+                            self.lock_path.display()
+                        );
+                        eprintln!("{}", message.yellow());
+                        is_waiting = true;
+                    }
+                    thread::sleep(POLL_INTERVAL);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        let permit = Permit { limiter: self, owner };
+
+        let last = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|contents| contents.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+        if let Some(last) = last {
+            let wait = (last + self.interval).saturating_sub(now());
+            if wait > Duration::ZERO {
+                let message = format!("waiting {:.1}s before the next request to the server", wait.as_secs_f64());
+                eprintln!("{}", message.yellow());
+                thread::sleep(wait);
+            }
+        }
+        Ok(permit)
+    }
+
+    /// The owner written into the lock file, if its holder has stopped
+    /// touching it.
+    fn stale_lock_owner(&self) -> Option<String> {
+        let owner = fs::read_to_string(&self.lock_path).ok()?;
+        let is_stale = fs::metadata(&self.lock_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > self.interval + LOCK_MARGIN);
+        is_stale.then_some(owner)
+    }
+
+    /// Removes the lock file, unless another process has taken it over from
+    /// `owner` in the meantime.
+    fn remove_lock(&self, owner: &str) {
+        if fs::read_to_string(&self.lock_path).is_ok_and(|contents| contents == owner) {
+            let _ = fs::remove_file(&self.lock_path);
+        }
+    }
+}
+
+impl Permit<'_> {
+    /// Marks the lock as still in use, for requests that take a while to
+    /// finish, e.g. because they are retried.
+    pub fn touch(&self) {
+        if let Ok(file) = OpenOptions::new().write(true).open(&self.limiter.lock_path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        // Rounded up, so that the interval is never cut short
+        let millis = now().as_millis() + 1;
+        let _ = fs::write(&self.limiter.path, millis.to_string());
+        self.limiter.remove_lock(&self.owner);
+    }
+}
+
+/// Identifies a lock holder, across processes and across the threads of a
+/// process.
+fn unique_owner() -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    format!(
+        "{} {} {}",
+        std::process::id(),
+        now().as_nanos(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// The current time since the Unix epoch, which unlike an `Instant` can be
+/// shared between processes.
+fn now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+#[test]
+fn rate_limiter_test() {
+    use std::time::Instant;
+    let dir = crate::mock_server::temp_dir("rate_limit");
+    let interval = Duration::from_millis(300);
+    let limiter = RateLimiter::new(dir.join("last_fetch"), interval);
+
+    let start = Instant::now();
+    drop(limiter.acquire().unwrap());
+    assert!(start.elapsed() < interval);
+    assert!(dir.join("last_fetch").exists());
+    assert!(!dir.join("last_fetch.lock").exists());
+
+    // Another process has to wait for the interval
+    let other = RateLimiter::new(dir.join("last_fetch"), interval);
+    let permit = other.acquire().unwrap();
+    assert!(start.elapsed() >= interval);
+    assert!(dir.join("last_fetch.lock").exists());
+
+    // And for the lock to be released
+    let waiter = thread::spawn(move || {
+        let start = Instant::now();
+        drop(limiter.acquire().unwrap());
+        start.elapsed()
+    });
+    thread::sleep(Duration::from_millis(100));
+    drop(permit);
+    assert!(waiter.join().unwrap() >= interval);
+
+    // A lock is only stale once its holder could no longer be waiting
+    let limiter = RateLimiter::new(dir.join("last_fetch"), interval);
+    let permit = limiter.acquire().unwrap();
+    let modified = SystemTime::now() - Duration::from_secs(90);
+    let set_modified = || {
+        fs::File::options()
+            .write(true)
+            .open(dir.join("last_fetch.lock"))
+            .unwrap()
+            .set_modified(modified)
+            .unwrap()
+    };
+    set_modified();
+    assert_eq!(limiter.stale_lock_owner(), Some(permit.owner.clone()));
+    assert_eq!(RateLimiter::new(dir.join("last_fetch"), Duration::from_secs(120)).stale_lock_owner(), None);
+    permit.touch();
+    assert_eq!(limiter.stale_lock_owner(), None);
+
+    // A permit whose lock was taken over leaves the new lock alone
+    set_modified();
+    let other = RateLimiter::new(dir.join("last_fetch"), Duration::ZERO);
+    let other_permit = other.acquire().unwrap();
+    drop(permit);
+    assert_eq!(fs::read_to_string(dir.join("last_fetch.lock")).unwrap(), other_permit.owner);
+    drop(other_permit);
+    assert!(!dir.join("last_fetch.lock").exists());
+}
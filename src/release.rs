@@ -0,0 +1,110 @@
+//! When puzzles unlock: at midnight EST (05:00 UTC) on December 1 through 25
+//! of the event year.
+
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The source of the current time, replaced in tests so that waiting for a
+/// puzzle doesn't take days.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// A clock that only moves when slept on.
+#[cfg(test)]
+pub struct MockClock(std::sync::Mutex<SystemTime>);
+
+#[cfg(test)]
+impl MockClock {
+    pub fn new(now: SystemTime) -> MockClock {
+        MockClock(std::sync::Mutex::new(now))
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64);
+    UNIX_EPOCH + Duration::from_secs(days as u64 * 86400 + 5 * 3600)
+}
+
+/// How long until the puzzle unlocks, or `None` if it already has.
+pub fn time_until_unlock(clock: &dyn Clock, year: u32, day: u32) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(clock.now())
+        .ok()
+        .filter(|&remaining| remaining > Duration::ZERO)
+}
+
+/// Formats the time left as e.g. `2d 03h 04m 05s`, leaving out leading zero
+/// units.
+pub fn format_countdown(remaining: Duration) -> String {
+    // Rounded up, so that the countdown doesn't show 0s while still locked
+    let secs = remaining.as_secs() + (remaining.subsec_nanos() > 0) as u64;
+    let (days, hours, minutes, seconds) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{}d {:02}h {:02}m {:02}s", days, hours, minutes, seconds)
+    } else if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// The number of days between 1970-01-01 and the given date in the proleptic
+/// Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[test]
+fn release_test() {
+    // 2015-12-01T05:00:00Z and 2015-12-25T05:00:00Z
+    assert_eq!(unlock_time(2015, 1), UNIX_EPOCH + Duration::from_secs(1448946000));
+    assert_eq!(unlock_time(2015, 25), UNIX_EPOCH + Duration::from_secs(1451019600));
+    assert_eq!(days_from_civil(1970, 1, 1), 0);
+    assert_eq!(days_from_civil(2000, 3, 1), 11017);
+
+    let clock = MockClock::new(unlock_time(2015, 1) - Duration::from_secs(90));
+    assert_eq!(time_until_unlock(&clock, 2015, 1), Some(Duration::from_secs(90)));
+    assert_eq!(
+        time_until_unlock(&clock, 2015, 3),
+        Some(Duration::from_secs(2 * 86400 + 90))
+    );
+    clock.sleep(Duration::from_secs(90));
+    assert_eq!(time_until_unlock(&clock, 2015, 1), None);
+
+    assert_eq!(format_countdown(Duration::from_millis(500)), "1s");
+    assert_eq!(format_countdown(Duration::from_secs(90)), "1m 30s");
+    assert_eq!(format_countdown(Duration::from_secs(2 * 86400 + 3 * 3600 + 4 * 60 + 5)), "2d 03h 04m 05s");
+}
@@ -0,0 +1,140 @@
+use crate::{Error, Result};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// The server's verdict on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// Submitted too soon after a previous answer, with the seconds to wait.
+    Wait(u64),
+    /// The part was already solved, so the answer wasn't checked.
+    AlreadySolved,
+}
+
+impl Outcome {
+    /// Whether the outcome is final for this answer, as opposed to the answer
+    /// not having been checked at all.
+    pub fn is_final(&self) -> bool {
+        !matches!(self, Outcome::Wait(_) | Outcome::AlreadySolved)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "incorrect, too high"),
+            Outcome::TooLow => write!(f, "incorrect, too low"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+            Outcome::Wait(secs) => write!(f, "submitted too recently, wait {}s", secs),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Interprets the HTML page returned after submitting an answer.
+pub fn parse_response(html: &str) -> Result<Outcome> {
+    lazy_static! {
+        static ref WAIT: Regex =
+            Regex::new(r"You have (?:(?P<m>\d+)m )?(?P<s>\d+)s left to wait").unwrap();
+    }
+    if html.contains("That's the right answer") {
+        Ok(Outcome::Correct)
+    } else if html.contains("your answer is too high") {
+        Ok(Outcome::TooHigh)
+    } else if html.contains("your answer is too low") {
+        Ok(Outcome::TooLow)
+    } else if html.contains("That's not the right answer") {
+        Ok(Outcome::Incorrect)
+    } else if let Some(captures) = WAIT.captures(html) {
+        let minutes: u64 = captures.name("m").map_or(Ok(0), |m| m.as_str().parse())?;
+        let seconds: u64 = captures["s"].parse()?;
+        Ok(Outcome::Wait(minutes * 60 + seconds))
+    } else if html.contains("You gave an answer too recently") {
+        Ok(Outcome::Wait(0))
+    } else if html.contains("You don't seem to be solving the right level") {
+        Ok(Outcome::AlreadySolved)
+    } else {
+        Err(Error::UnrecognizedResponse)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Every submitted answer and its outcome, keyed by `dayXX partN`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Submissions {
+    submissions: BTreeMap<String, Vec<Submission>>,
+}
+
+impl Submissions {
+    pub fn load(path: &Path) -> Result<Submissions> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// The final outcome of a previous submission of the same answer, if any.
+    pub fn find(&self, day: &str, part: usize, answer: &str) -> Option<Outcome> {
+        self.submissions
+            .get(&format!("{} part{}", day, part))?
+            .iter()
+            .find(|submission| submission.answer == answer && submission.outcome.is_final())
+            .map(|submission| submission.outcome)
+    }
+
+    pub fn insert(&mut self, day: &str, part: usize, answer: String, outcome: Outcome) {
+        self.submissions
+            .entry(format!("{} part{}", day, part))
+            .or_default()
+            .push(Submission { answer, outcome });
+    }
+}
+
+#[test]
+fn parse_response_test() {
+    assert_eq!(
+        parse_response("<article><p>That's the right answer!  You are one gold star closer to powering the weather machine.</p></article>").unwrap(),
+        Outcome::Correct
+    );
+    assert_eq!(
+        parse_response("<article><p>That's not the right answer; your answer is too high.  If you're stuck, ...</p></article>").unwrap(),
+        Outcome::TooHigh
+    );
+    assert_eq!(
+        parse_response("<article><p>That's not the right answer; your answer is too low.</p></article>").unwrap(),
+        Outcome::TooLow
+    );
+    assert_eq!(
+        parse_response("<article><p>That's not the right answer.  If you're stuck, ...</p></article>").unwrap(),
+        Outcome::Incorrect
+    );
+    assert_eq!(
+        parse_response("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 7s left to wait.</p></article>").unwrap(),
+        Outcome::Wait(67)
+    );
+    assert_eq!(
+        parse_response("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>").unwrap(),
+        Outcome::AlreadySolved
+    );
+    assert!(parse_response("<html></html>").is_err());
+}
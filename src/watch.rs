@@ -0,0 +1,232 @@
+//! Runs a day again whenever its input changes, and with the `cargo` option,
+//! whenever the sources change as well. Changes are detected by polling the
+//! modification times of the watched files.
+
+use crate::bench::format_duration;
+use crate::framework::{self, Framework, PartResult};
+use crate::output::Format;
+use crate::Result;
+use colored::*;
+use reqwest::blocking::Client;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// How often the watched files are checked for changes.
+    pub interval: Duration,
+    /// Run the day with `cargo run` instead of in this process, so that it's
+    /// rebuilt when the sources change.
+    pub cargo: bool,
+    /// The command line options passed on to `cargo run`.
+    pub args: Vec<String>,
+}
+
+/// The answer of a part, or the error it failed with.
+pub type Answer = std::result::Result<String, String>;
+
+/// The answers of the parts that ran, or why the day couldn't run at all.
+pub type Run = std::result::Result<Vec<(usize, Answer)>, String>;
+
+/// The size and modification time of every watched file, with directories
+/// replaced by the files in them. Files that don't exist are included as
+/// well, so that creating them counts as a change.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, Option<(SystemTime, u64)>>);
+
+impl Snapshot {
+    pub fn take(paths: &[PathBuf]) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+        for path in paths {
+            snapshot.add(path);
+        }
+        snapshot
+    }
+
+    fn add(&mut self, path: &Path) {
+        let metadata = fs::metadata(path).ok();
+        if metadata.as_ref().is_some_and(|metadata| metadata.is_dir()) {
+            if let Ok(entries) = fs::read_dir(path) {
+                for entry in entries.flatten() {
+                    self.add(&entry.path());
+                }
+            }
+            return;
+        }
+        let stamp = metadata.and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+        self.0.insert(path.to_owned(), stamp);
+    }
+}
+
+/// Runs a day, and runs it again whenever one of the watched files changes,
+/// until the process is interrupted. Each run clears the screen and shows the
+/// answers, marking the ones that changed since the previous run.
+pub fn run(fw: &mut Framework, client: &Client, day: &str, variant: Option<&str>, options: &Options) -> Result<()> {
+    // The answers are printed by `render` instead
+    fw.set_options(framework::Options {
+        format: Format::Json,
+        ..fw.options().clone()
+    });
+    let title = framework::title(day, variant);
+
+    let mut previous: Option<Run> = None;
+    loop {
+        let mut paths = fw.input_files(day, variant)?;
+        if options.cargo {
+            paths.push(PathBuf::from("Cargo.toml"));
+            paths.push(PathBuf::from("src"));
+        }
+        let snapshot = Snapshot::take(&paths);
+
+        // Cleared before the run, so that build errors stay visible
+        print!("\x1b[2J\x1b[H");
+        let start = Instant::now();
+        let run = if options.cargo {
+            run_cargo(&title, &options.args)?
+        } else {
+            run_in_process(fw, client, day, variant)
+        };
+        print!("{}", render(&title, &run, previous.as_ref(), start.elapsed()));
+        let watched = paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>();
+        println!("{}", format!("watching {}", watched.join(", ")).dimmed());
+        previous = Some(run);
+
+        while Snapshot::take(&paths) == snapshot {
+            thread::sleep(options.interval);
+        }
+    }
+}
+
+fn run_in_process(fw: &mut Framework, client: &Client, day: &str, variant: Option<&str>) -> Run {
+    let mut run = Err(String::new());
+    fw.execute(client, &[(day, variant)], |_, _, result| {
+        run = match result {
+            Ok(day_run) => Ok(day_run
+                .parts
+                .into_iter()
+                .map(|part| {
+                    let answer = match part.result {
+                        PartResult::Answer(answer) => Ok(answer),
+                        PartResult::Error(e) => Err(e.to_string()),
+                        PartResult::Panic(message) => Err(format!("panicked: {}", message)),
+                        PartResult::Timeout(duration) => Err(format!("timed out after {:?}", duration)),
+                    };
+                    (part.part, answer)
+                })
+                .collect()),
+            Err(e) => Err(e.to_string()),
+        }
+    });
+    run
+}
+
+/// A record printed by `run --format json`, see [`crate::output::Record`].
+#[derive(Deserialize)]
+struct ChildRecord {
+    part: usize,
+    answer: Option<String>,
+    error: Option<String>,
+}
+
+/// Runs the day with `cargo run`, in the same profile as this process.
+/// Cargo's own output (such as build errors) goes straight to the terminal.
+fn run_cargo(title: &str, args: &[String]) -> Result<Run> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command.args(["run", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    command
+        .args(["--", "run", title, "--format", "json"])
+        .args(args)
+        .stderr(Stdio::inherit());
+    let output = command.output()?;
+    if output.stdout.is_empty() {
+        return Ok(Err(match output.status.code() {
+            Some(code) => format!("cargo run failed with exit code {}", code),
+            None => "cargo run was terminated".to_owned(),
+        }));
+    }
+    parse_records(&output.stdout)
+}
+
+fn parse_records(json: &[u8]) -> Result<Run> {
+    let records: Vec<ChildRecord> = serde_json::from_slice(json)?;
+    Ok(Ok(records
+        .into_iter()
+        .map(|record| {
+            let answer = match record.answer {
+                Some(answer) => Ok(answer),
+                None => Err(record.error.unwrap_or_default()),
+            };
+            (record.part, answer)
+        })
+        .collect()))
+}
+
+/// Formats the answers of a run, along with the answers they replace when
+/// they differ from the previous run.
+pub fn render(title: &str, run: &Run, previous: Option<&Run>, elapsed: Duration) -> String {
+    let mut out = String::new();
+    match run {
+        Ok(parts) => {
+            let previous = previous.and_then(|previous| previous.as_ref().ok());
+            for (part, answer) in parts {
+                let old = previous.and_then(|parts| parts.iter().find(|(p, _)| p == part)).map(|(_, old)| old);
+                let changed = old.is_some_and(|old| old != answer);
+                let text = match answer {
+                    Ok(answer) if changed => answer.bright_yellow(),
+                    Ok(answer) => answer.bright_green(),
+                    Err(e) => e.bright_red(),
+                };
+                out += &format!("{} {}", format!("{} part{}", title, part).bright_cyan().bold(), text);
+                match old {
+                    Some(Ok(old)) if changed => out += &format!(" (was {})", old),
+                    Some(Err(_)) if changed => out += " (was an error)",
+                    _ => {}
+                }
+                out.push('\n');
+            }
+        }
+        Err(e) => out += &format!("{}\n", format!("{}: {}", title, e).bright_red()),
+    }
+    out += &format!("{}\n", format!("took {}", format_duration(elapsed)).dimmed());
+    out
+}
+
+#[test]
+fn watch_test() {
+    colored::control::set_override(false);
+    let dir = crate::mock_server::temp_dir("watch");
+    let input = dir.join("day09.txt");
+    let snapshot = Snapshot::take(&[input.clone(), dir.clone()]);
+    fs::write(&input, "London to Dublin = 464").unwrap();
+    assert_ne!(Snapshot::take(&[input.clone(), dir.clone()]), snapshot);
+
+    let json = br#"[
+  {"name": "day09", "part": 1, "status": "OK", "answer": "605", "duration": 0.1, "error": null, "input_hash": "x"},
+  {"name": "day09", "part": 2, "status": "ERR", "answer": null, "duration": 0.1, "error": "no route", "input_hash": "x"}
+]"#;
+    let previous = parse_records(json).unwrap();
+    assert_eq!(previous, Ok(vec![(1, Ok("605".to_owned())), (2, Err("no route".to_owned()))]));
+
+    let run = Ok(vec![(1, Ok("605".to_owned())), (2, Ok("982".to_owned()))]);
+    assert_eq!(
+        render("day09", &run, Some(&previous), Duration::from_millis(5)),
+        "day09 part1 605\nday09 part2 982 (was an error)\ntook 5.00ms\n"
+    );
+    let run = Ok(vec![(1, Ok("600".to_owned()))]);
+    assert_eq!(
+        render("day09", &run, Some(&previous), Duration::from_millis(5)),
+        "day09 part1 600 (was 605)\ntook 5.00ms\n"
+    );
+    assert_eq!(
+        render("day09", &Err("bad input".to_owned()), None, Duration::from_secs(2)),
+        "day09: bad input\ntook 2.00s\n"
+    );
+}
@@ -0,0 +1,32 @@
+//! Uses the days through the public API, the way another crate would.
+
+use advent_of_code_2015::day07::evaluate;
+use advent_of_code_2015::day18::LightGrid;
+use advent_of_code_2015::{day09, day17, day23, ParseOptions, Solution, DAYS};
+
+#[test]
+fn solver_api_test() {
+    assert_eq!(DAYS.len(), 25);
+
+    let input = "London to Dublin = 464\nLondon to Belfast = 518\nDublin to Belfast = 141";
+    let routes = day09::Solver::parse(input, &ParseOptions::default()).unwrap();
+    assert_eq!(day09::Solver::part1(&routes, &day09::Params::default()).unwrap(), "605");
+    assert_eq!(day09::Solver::part2(&routes, &day09::Params::default()).unwrap(), "982");
+
+    let sizes = day17::Solver::parse("20\n15\n10\n5\n5", &ParseOptions::default()).unwrap();
+    let params = day17::Params { liters: 25 };
+    assert_eq!(day17::Solver::part1(&sizes, &params).unwrap(), "4");
+    assert_eq!(day17::Solver::part2(&sizes, &params).unwrap(), "3");
+
+    let program = day23::parse_input("inc b\njio b, +2\ntpl b\ninc b").unwrap();
+    assert_eq!(day23::solve(&program, 0).unwrap(), 2);
+
+    let circuit = advent_of_code_2015::day07::transform("123 -> x\nx LSHIFT 2 -> f", &ParseOptions::default()).unwrap();
+    assert_eq!(evaluate(&circuit, &[])["f"], 492);
+    let lenient = ParseOptions { lenient: true };
+    assert!(day09::Solver::parse("London to Dublin = 464\nDublin", &ParseOptions::default()).is_err());
+    assert!(day09::Solver::parse("London to Dublin = 464\nDublin", &lenient).is_ok());
+
+    let grid: LightGrid = ".#.\n.#.\n.#.".parse().unwrap();
+    assert_eq!(grid.transform_1().to_string(), "...\n###\n...");
+}